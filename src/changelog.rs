@@ -0,0 +1,272 @@
+use crate::{commit::ConventionalCommit, git};
+use anyhow::Result;
+use std::fmt;
+
+/// `(type, title, shown)` in the order sections appear. Hidden sections are
+/// grouped but left out of rendered changelogs.
+pub const SECTIONS: [(&str, &str, bool); 11] = [
+    ("feat", "Features", true),
+    ("fix", "Bug Fixes", true),
+    ("perf", "Performance Improvements", true),
+    ("revert", "Reverts", true),
+    ("docs", "Documentation", false),
+    ("style", "Styles", false),
+    ("refactor", "Code Refactoring", false),
+    ("test", "Tests", false),
+    ("build", "Build System", false),
+    ("ci", "Continuous Integration", false),
+    ("chore", "Chores", false),
+];
+
+const FIELD_SEPARATOR: char = '\x1f';
+const RECORD_SEPARATOR: char = '\x1e';
+
+/// A conventional commit read from the history.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LogEntry {
+    pub hash: String,
+    pub author: String,
+    pub commit: ConventionalCommit,
+    /// Paths touched by the commit, relative to the work tree root.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub files: Vec<String>,
+}
+
+impl LogEntry {
+    pub fn short_hash(&self) -> &str {
+        &self.hash[..self.hash.len().min(7)]
+    }
+}
+
+/// Conventional commits in `range` (`git log` syntax, such as `v1.0.0..HEAD`),
+/// newest first. Commits that don't follow the format are skipped.
+pub fn read_log(range: &str) -> Result<Vec<LogEntry>> {
+    // The file list of `--name-only` follows the last field of each record
+    let format = format!(
+        "--format={}%H{}%an{}%B{}",
+        RECORD_SEPARATOR, FIELD_SEPARATOR, FIELD_SEPARATOR, FIELD_SEPARATOR
+    );
+    let output = git::output(&["log", "--name-only", &format, range])?;
+    Ok(parse_log(&output))
+}
+
+/// Parses the output of `git log` in the format used by [`read_log`].
+pub fn parse_log(output: &str) -> Vec<LogEntry> {
+    output
+        .split(RECORD_SEPARATOR)
+        .filter_map(|record| {
+            let mut fields = record.splitn(4, FIELD_SEPARATOR);
+            let hash = fields.next()?.trim().to_string();
+            let author = fields.next()?.to_string();
+            let commit = ConventionalCommit::parse(fields.next()?.trim()).ok()?;
+            let files = fields
+                .next()
+                .unwrap_or_default()
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(str::to_string)
+                .collect();
+            Some(LogEntry { hash, author, commit, files })
+        })
+        .collect()
+}
+
+/// Commits of one type.
+#[derive(Debug)]
+pub struct Section<'a> {
+    pub commit_type: String,
+    pub title: String,
+    /// Whether the section is part of rendered changelogs.
+    pub shown: bool,
+    pub entries: Vec<&'a LogEntry>,
+}
+
+/// Groups entries by type, in [`SECTIONS`] order followed by unknown types.
+pub fn group(entries: &[LogEntry]) -> Vec<Section<'_>> {
+    let mut sections: Vec<Section> = Vec::new();
+
+    for entry in entries {
+        let commit_type = &entry.commit.commit_type;
+        match sections.iter_mut().find(|s| s.commit_type == *commit_type) {
+            Some(section) => section.entries.push(entry),
+            None => {
+                let known = SECTIONS.iter().find(|(t, _, _)| t == commit_type);
+                sections.push(Section {
+                    commit_type: commit_type.clone(),
+                    title: known.map_or_else(|| commit_type.clone(), |(_, title, _)| title.to_string()),
+                    shown: known.is_some_and(|(_, _, shown)| *shown),
+                    entries: vec![entry],
+                });
+            }
+        }
+    }
+
+    let order = |section: &Section| {
+        SECTIONS
+            .iter()
+            .position(|(t, _, _)| *t == section.commit_type)
+            .unwrap_or(SECTIONS.len())
+    };
+    sections.sort_by_key(order);
+    sections
+}
+
+pub fn breaking_changes(entries: &[LogEntry]) -> Vec<&LogEntry> {
+    entries.iter().filter(|e| e.commit.breaking_change).collect()
+}
+
+/// Semantic version increment implied by a set of commits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Bump {
+    None,
+    Patch,
+    Minor,
+    Major,
+}
+
+pub fn bump_level(entries: &[LogEntry]) -> Bump {
+    entries
+        .iter()
+        .map(|entry| match entry.commit.commit_type.as_str() {
+            _ if entry.commit.breaking_change => Bump::Major,
+            "feat" => Bump::Minor,
+            "fix" | "perf" => Bump::Patch,
+            _ => Bump::None,
+        })
+        .max()
+        .unwrap_or(Bump::None)
+}
+
+/// A `major.minor.patch` version, optionally written with a `v` prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl Version {
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.strip_prefix('v').unwrap_or(text);
+        let mut parts = text.split('.').map(|p| p.parse::<u64>().ok());
+        let version = Self {
+            major: parts.next()??,
+            minor: parts.next()??,
+            patch: parts.next()??,
+        };
+        parts.next().is_none().then_some(version)
+    }
+
+    /// The next version. Before 1.0.0, breaking changes bump the minor
+    /// version and features the patch version.
+    pub fn bump(self, level: Bump) -> Self {
+        let level = match (self.major, level) {
+            (0, Bump::Major) => Bump::Minor,
+            (0, Bump::Minor) => Bump::Patch,
+            (_, level) => level,
+        };
+        match level {
+            Bump::None => self,
+            Bump::Patch => Self { patch: self.patch + 1, ..self },
+            Bump::Minor => Self { minor: self.minor + 1, patch: 0, ..self },
+            Bump::Major => Self { major: self.major + 1, minor: 0, patch: 0 },
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// One changelog line: `**scope:** description (hash)`.
+pub fn format_entry(entry: &LogEntry) -> String {
+    let scope = entry
+        .commit
+        .scope
+        .as_ref()
+        .map(|s| format!("**{}:** ", s))
+        .unwrap_or_default();
+    format!("{}{} ({})", scope, entry.commit.description, entry.short_hash())
+}
+
+/// Renders a Markdown changelog section for one release.
+pub fn render_markdown(heading: &str, entries: &[LogEntry]) -> String {
+    let mut out = format!("## {}\n", heading);
+
+    let breaking = breaking_changes(entries);
+    if !breaking.is_empty() {
+        out.push_str("\n### ⚠ BREAKING CHANGES\n\n");
+        for entry in breaking {
+            let description = match entry.commit.breaking_change_description.trim() {
+                "" => format_entry(entry),
+                text => text.to_string(),
+            };
+            out.push_str(&format!("* {}\n", description));
+        }
+    }
+
+    for section in group(entries).iter().filter(|s| s.shown) {
+        out.push_str(&format!("\n### {}\n\n", section.title));
+        for entry in &section.entries {
+            out.push_str(&format!("* {}\n", format_entry(entry)));
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(text: &str) -> Version {
+        Version::parse(text).unwrap()
+    }
+
+    fn entry(message: &str) -> LogEntry {
+        LogEntry {
+            hash: "0123456789abcdef".to_string(),
+            author: "Jane".to_string(),
+            commit: ConventionalCommit::parse(message).unwrap(),
+            files: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn parses_versions() {
+        assert_eq!(version("v1.2.3"), Version { major: 1, minor: 2, patch: 3 });
+        assert_eq!(Version::parse("1.2"), None);
+        assert_eq!(Version::parse("1.2.3.4"), None);
+        assert_eq!(Version::parse("1.x.3"), None);
+    }
+
+    #[test]
+    fn bumps_stable_versions() {
+        assert_eq!(version("1.2.3").bump(Bump::None), version("1.2.3"));
+        assert_eq!(version("1.2.3").bump(Bump::Patch), version("1.2.4"));
+        assert_eq!(version("1.2.3").bump(Bump::Minor), version("1.3.0"));
+        assert_eq!(version("1.2.3").bump(Bump::Major), version("2.0.0"));
+    }
+
+    #[test]
+    fn bumps_initial_development_versions_one_level_lower() {
+        assert_eq!(version("0.2.3").bump(Bump::Major), version("0.3.0"));
+        assert_eq!(version("0.2.3").bump(Bump::Minor), version("0.2.4"));
+        assert_eq!(version("0.2.3").bump(Bump::Patch), version("0.2.4"));
+    }
+
+    #[test]
+    fn bump_level_is_the_highest_of_the_commits() {
+        assert_eq!(bump_level(&[]), Bump::None);
+        assert_eq!(bump_level(&[entry("docs: x"), entry("chore: y")]), Bump::None);
+        assert_eq!(bump_level(&[entry("docs: x"), entry("perf: y")]), Bump::Patch);
+        assert_eq!(bump_level(&[entry("fix: x"), entry("feat: y")]), Bump::Minor);
+        assert_eq!(bump_level(&[entry("feat: x"), entry("chore!: y")]), Bump::Major);
+    }
+}
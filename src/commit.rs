@@ -1,13 +1,230 @@
 use regex::Regex;
+use std::sync::LazyLock;
 
-#[derive(Debug)]
+/// A trailer line such as `Refs: #123`, `Reviewed-by: Jane` or `Closes #42`.
+static FOOTER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?P<token>BREAKING CHANGE|[A-Za-z][A-Za-z0-9-]*)(?P<separator>: | #)(?P<value>.*)$").unwrap()
+});
+
+/// A gitmoji shortcode such as `:sparkles:`, or a unicode emoji with its
+/// variation selectors, skin tones and ZWJ sequences. Symbols that are text
+/// by default, such as `©` or `↔`, only count with the emoji selector U+FE0F.
+const EMOJI: &str = r":[a-z0-9_+-]+:|(?:\p{Emoji_Presentation}|\p{Extended_Pictographic}\x{FE0F})(?:\x{FE0F}|\x{200D}\p{Extended_Pictographic}|[\x{1F3FB}-\x{1F3FF}])*";
+
+static HEADER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"^(?:(?P<lead>{emoji}) )?(?P<type>[a-z]+)(\((?P<scope>[^()\r\n]*)\))?(?P<breaking>!)?: (?:(?P<emoji>{emoji}) )?(?P<description>[^\r\n]+)",
+        emoji = EMOJI
+    ))
+    .unwrap()
+});
+
+/// Footer tokens announcing a breaking change. Both are equivalent.
+pub const BREAKING_TOKENS: [&str; 2] = ["BREAKING CHANGE", "BREAKING-CHANGE"];
+
+/// First words of non-conventional messages and the type they suggest.
+const KEYWORD_TYPES: [(&str, &[&str]); 9] = [
+    (
+        "fix",
+        &["fix", "fixed", "fixes", "fixing", "bugfix", "hotfix", "resolve", "resolved", "correct", "corrected"],
+    ),
+    (
+        "feat",
+        &[
+            "add", "added", "adds", "adding", "implement", "implemented", "introduce", "introduced",
+            "support", "create", "created", "new",
+        ],
+    ),
+    ("docs", &["doc", "docs", "document", "documented", "documentation"]),
+    (
+        "refactor",
+        &[
+            "refactor", "refactored", "rename", "renamed", "move", "moved", "extract", "extracted",
+            "simplify", "simplified", "cleanup", "clean",
+        ],
+    ),
+    ("test", &["test", "tests", "tested", "testing"]),
+    ("perf", &["perf", "optimize", "optimized", "optimise", "optimised", "speed"]),
+    ("build", &["bump", "bumped", "upgrade", "upgraded", "deps", "dependencies"]),
+    ("ci", &["ci", "pipeline", "workflow"]),
+    ("style", &["format", "formatted", "formatting", "lint", "style"]),
+];
+
+/// Header prefixes git generates for merges, reverts and autosquash commits.
+const EXEMPT_PREFIXES: [&str; 5] = ["Merge ", "Revert \"", "fixup! ", "squash! ", "amend! "];
+
+/// Characters git chooses from, in this order, with `core.commentChar=auto`.
+pub const AUTO_COMMENT_CHARS: [char; 10] = ['#', ';', '@', '!', '$', '%', '^', '&', '|', ':'];
+
+/// Removes comment lines and everything below the scissors line, like `git commit --cleanup=strip`.
+pub fn strip_comments(message: &str, comment_char: char) -> String {
+    strip_comment_prefix(message, &comment_char.to_string())
+}
+
+/// Like [`strip_comments`] with a comment string of any length, as git
+/// allows in `core.commentChar` and `core.commentString`.
+pub fn strip_comment_prefix(message: &str, prefix: &str) -> String {
+    let scissors = scissors_line(prefix);
+
+    message
+        .lines()
+        .take_while(|line| *line != scissors)
+        .filter(|line| !line.starts_with(prefix))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// Like [`strip_comments`] for `core.commentChar=auto`, where git picks the
+/// first of [`AUTO_COMMENT_CHARS`] no line of the message starts with. The
+/// scissors line tells which one it used; without one, lines starting with
+/// any of them are removed.
+pub fn strip_auto_comments(message: &str) -> String {
+    let used = AUTO_COMMENT_CHARS
+        .into_iter()
+        .find(|c| message.lines().any(|line| line == scissors_line(&c.to_string())));
+    if let Some(comment_char) = used {
+        return strip_comments(message, comment_char);
+    }
+
+    message
+        .lines()
+        .filter(|line| !line.starts_with(AUTO_COMMENT_CHARS))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+fn scissors_line(prefix: &str) -> String {
+    format!("{} ------------------------ >8 ------------------------", prefix)
+}
+
+/// Whether the message was generated by git and should not be validated.
+pub fn is_exempt(message: &str) -> bool {
+    let header = message.lines().next().unwrap_or_default();
+    EXEMPT_PREFIXES.iter().any(|prefix| header.starts_with(prefix))
+}
+
+/// A trailer in the footer section, other than `BREAKING CHANGE`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Footer {
+    pub token: String,
+    pub value: String,
+}
+
+impl Footer {
+    pub fn new(token: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Parses `TOKEN=VALUE`, as given on the command line.
+    pub fn from_assignment(text: &str) -> Result<Self, String> {
+        match text.split_once('=') {
+            Some((token, value)) if !token.trim().is_empty() => {
+                Ok(Self::new(token.trim(), value.trim()))
+            }
+            _ => Err(format!("Invalid footer `{}`, expected TOKEN=VALUE", text)),
+        }
+    }
+
+    fn render(&self) -> String {
+        format!("{}: {}", self.token, self.value)
+    }
+}
+
+/// Where an emoji goes in the header.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum EmojiPosition {
+    /// `:sparkles: feat: add login`
+    Prefix,
+    /// `feat: ✨ add login`
+    Description,
+}
+
+/// An emoji decorating the header, kept apart from the description so
+/// changelogs and validation see clean text.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Emoji {
+    pub text: String,
+    pub position: EmojiPosition,
+}
+
+/// How a breaking change is announced.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum BreakingMarker {
+    /// `feat!: ...`, with a footer only when there is a description.
+    Bang,
+    /// `BREAKING CHANGE: ...` footer only.
+    Footer,
+    /// Both the `!` and, when there is a description, the footer.
+    Both,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BreakingStyle {
+    /// `style` in the `[breaking]` table.
+    #[cfg_attr(feature = "serde", serde(rename = "style"))]
+    pub marker: BreakingMarker,
+    /// One of [`BREAKING_TOKENS`].
+    pub token: &'static str,
+}
+
+/// The token is read as an owned string and mapped back to one of
+/// [`BREAKING_TOKENS`], so styles can be deserialized from borrowed input.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for BreakingStyle {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        struct Raw {
+            #[serde(rename = "style")]
+            marker: BreakingMarker,
+            token: String,
+        }
+
+        let raw = Raw::deserialize(deserializer)?;
+        let token = BREAKING_TOKENS
+            .into_iter()
+            .find(|t| *t == raw.token)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown breaking change token `{}`", raw.token)))?;
+        Ok(Self { marker: raw.marker, token })
+    }
+}
+
+impl Default for BreakingStyle {
+    fn default() -> Self {
+        Self {
+            marker: BreakingMarker::Bang,
+            token: BREAKING_TOKENS[0],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct ConventionalCommit {
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
     pub commit_type: String,
     pub scope: Option<String>,
     pub description: String,
     pub body: Option<String>,
     pub breaking_change: bool,
     pub breaking_change_description: String,
+    pub footers: Vec<Footer>,
+    pub emoji: Option<Emoji>,
+    pub breaking_style: BreakingStyle,
 }
 
 impl Default for ConventionalCommit {
@@ -19,6 +236,9 @@ impl Default for ConventionalCommit {
             body: None,
             breaking_change: false,
             breaking_change_description: "".to_string(),
+            footers: Vec::new(),
+            emoji: None,
+            breaking_style: BreakingStyle::default(),
         }
     }
 }
@@ -30,11 +250,22 @@ impl ConventionalCommit {
             .map(|s| format!("({})", s))
             .unwrap_or_default();
 
-        let breaking = if self.breaking_change { "!" } else { "" };
+        let marker = self.breaking_style.marker;
+        let breaking = if self.breaking_change && marker != BreakingMarker::Footer {
+            "!"
+        } else {
+            ""
+        };
+
+        let (lead, emoji) = match &self.emoji {
+            Some(e) if e.position == EmojiPosition::Prefix => (format!("{} ", e.text), String::new()),
+            Some(e) => (String::new(), format!("{} ", e.text)),
+            None => (String::new(), String::new()),
+        };
 
         let header = format!(
-            "{}{}{}: {}",
-            self.commit_type, scope, breaking, self.description
+            "{}{}{}{}: {}{}",
+            lead, self.commit_type, scope, breaking, emoji, self.description
         );
 
         let body = self.body
@@ -42,34 +273,331 @@ impl ConventionalCommit {
             .map(|b| format!("\n\n{}", b))
             .unwrap_or_default();
 
-        let footer = if self.breaking_change {
-            let breaking_desc = if !self.breaking_change_description.is_empty() {
-                format!("\n\nBREAKING CHANGE: {}", self.breaking_change_description)
-            } else {
-                "".to_string()
+        let mut trailers = Vec::new();
+        if self.breaking_change {
+            // Without a description of its own, only the footer style repeats the
+            // header's, as the footer is then the only breaking change marker
+            let description = match self.breaking_change_description.trim() {
+                "" if marker != BreakingMarker::Footer => None,
+                "" => Some(self.description.as_str()),
+                description => Some(description),
             };
-            breaking_desc
-        } else {
+            if let Some(description) = description {
+                trailers.push(format!("{}: {}", self.breaking_style.token, description));
+            }
+        }
+        trailers.extend(self.footers.iter().map(Footer::render));
+
+        let footer = if trailers.is_empty() {
             "".to_string()
+        } else {
+            format!("\n\n{}", trailers.join("\n"))
         };
 
         format!("{}{}{}", header, body, footer)
     }
 
-    pub fn validate(message: &str) -> Result<(), String> {
-        let re = Regex::new(r"^(?P<type>[a-z]+)(\((?P<scope>[^()\r\n]*)\))?(?P<breaking>!)?: (?P<description>[^\r\n]+)").unwrap();
+    /// Parses a commit message into its conventional parts.
+    pub fn parse(message: &str) -> Result<Self, String> {
+        let first_line = message
+            .lines()
+            .next()
+            .filter(|line| !line.trim().is_empty())
+            .ok_or_else(|| "Commit message is empty".to_string())?;
+
+        let captures = HEADER.captures(first_line).ok_or_else(|| {
+            format!(
+                "Invalid commit message format. Must follow:\n<type>[optional scope]: <description>\n\nExample: feat(parser): add new parsing algorithm\n\nYour message: {}",
+                first_line
+            )
+        })?;
+
+        let mut commit = Self {
+            commit_type: captures["type"].to_string(),
+            scope: captures
+                .name("scope")
+                .map(|s| s.as_str().to_string())
+                .filter(|s| !s.is_empty()),
+            description: captures["description"].to_string(),
+            body: None,
+            breaking_change: captures.name("breaking").is_some(),
+            breaking_change_description: String::new(),
+            footers: Vec::new(),
+            emoji: None,
+            breaking_style: BreakingStyle::default(),
+        };
+        if commit.breaking_change {
+            commit.breaking_style.marker = BreakingMarker::Bang;
+        }
+
+        if let Some(lead) = captures.name("lead") {
+            commit.emoji = Some(Emoji {
+                text: lead.as_str().to_string(),
+                position: EmojiPosition::Prefix,
+            });
+        } else if let Some(emoji) = captures.name("emoji") {
+            commit.emoji = Some(Emoji {
+                text: emoji.as_str().to_string(),
+                position: EmojiPosition::Description,
+            });
+        }
+
+        let rest = message.lines().skip(1).collect::<Vec<_>>().join("\n");
+        let mut paragraphs: Vec<&str> = rest.trim().split("\n\n").collect();
+
+        // The footer section is the last paragraph, when it starts with a trailer
+        let has_footer = paragraphs
+            .last()
+            .and_then(|p| p.lines().next())
+            .is_some_and(|line| FOOTER.is_match(line));
+        if has_footer && let Some(footer) = paragraphs.pop() {
+            commit.parse_footers(footer);
+        }
+
+        let body = paragraphs.join("\n\n").trim().to_string();
+        if !body.is_empty() {
+            commit.body = Some(body);
+        }
+
+        Ok(commit)
+    }
+
+    fn parse_footers(&mut self, section: &str) {
+        let mut footers: Vec<Footer> = Vec::new();
+
+        for line in section.lines() {
+            match FOOTER.captures(line) {
+                Some(captures) => {
+                    let value = if &captures["separator"] == " #" {
+                        format!("#{}", &captures["value"])
+                    } else {
+                        captures["value"].to_string()
+                    };
+                    footers.push(Footer::new(&captures["token"], value));
+                }
+                // Continuation of a multi-line footer value
+                None => {
+                    if let Some(last) = footers.last_mut() {
+                        last.value.push('\n');
+                        last.value.push_str(line);
+                    }
+                }
+            }
+        }
 
-        if let Some(first_line) = message.lines().next() {
-            if re.is_match(first_line) {
-                Ok(())
+        let has_bang = self.breaking_change;
+        for footer in footers {
+            if let Some(token) = BREAKING_TOKENS.iter().find(|t| **t == footer.token) {
+                self.breaking_change = true;
+                self.breaking_change_description = footer.value;
+                self.breaking_style = BreakingStyle {
+                    marker: if has_bang {
+                        BreakingMarker::Both
+                    } else {
+                        BreakingMarker::Footer
+                    },
+                    token,
+                };
             } else {
-                Err(format!(
-                    "Invalid commit message format. Must follow:\n<type>[optional scope]: <description>\n\nExample: feat(parser): add new parsing algorithm\n\nYour message: {}",
-                    first_line
-                ))
+                self.footers.push(footer);
             }
-        } else {
-            Err("Commit message is empty".to_string())
         }
     }
-}
\ No newline at end of file
+
+    /// Like [`parse`](Self::parse), but keeps a non-conventional message as
+    /// description and body so it can be fixed up.
+    pub fn parse_lenient(message: &str) -> Self {
+        Self::parse(message).unwrap_or_else(|_| {
+            let mut lines = message.trim().lines();
+            let description = lines.next().unwrap_or_default().trim().to_string();
+            let body = lines.collect::<Vec<_>>().join("\n").trim().to_string();
+
+            Self {
+                description,
+                body: Some(body).filter(|b| !b.is_empty()),
+                ..Self::default()
+            }
+        })
+    }
+
+    /// Like [`parse_lenient`](Self::parse_lenient), but guesses the type of
+    /// a non-conventional message from its first word, such as `fix` for
+    /// "Fixed crash" or `docs` for "Update README".
+    pub fn guess(message: &str) -> Self {
+        if let Ok(commit) = Self::parse(message) {
+            return commit;
+        }
+
+        let mut commit = Self::parse_lenient(message);
+        let words: Vec<String> = commit
+            .description
+            .split_whitespace()
+            .take(3)
+            .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+            .collect();
+        let guessed = KEYWORD_TYPES.iter().find(|(_, keywords)| {
+            words.first().is_some_and(|first| keywords.contains(&first.as_str()))
+        });
+        let mentions_docs = words.iter().any(|w| ["readme", "docs", "documentation"].contains(&w.as_str()));
+
+        if mentions_docs {
+            commit.commit_type = "docs".to_string();
+        } else if let Some((commit_type, _)) = guessed {
+            commit.commit_type = commit_type.to_string();
+        }
+
+        // Headers start lowercase, except for words like `README` or `API`
+        let mut chars = commit.description.chars();
+        if let (Some(first), Some(second)) = (chars.next(), chars.next())
+            && first.is_uppercase()
+            && !second.is_uppercase()
+        {
+            commit.description = first.to_lowercase().chain(commit.description.chars().skip(1)).collect();
+        }
+        commit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(message: &str) -> ConventionalCommit {
+        ConventionalCommit::parse(message).unwrap()
+    }
+
+    /// Messages that survive a parse and generate round trip unchanged.
+    const ROUND_TRIPS: [&str; 10] = [
+        "feat: add login",
+        "fix(parser): handle empty input",
+        "feat(api)!: drop v1 endpoints",
+        "feat!: drop v1 endpoints\n\nBREAKING CHANGE: clients must use v2",
+        "feat: drop v1 endpoints\n\nBREAKING CHANGE: clients must use v2",
+        "feat!: drop v1 endpoints\n\nBREAKING-CHANGE: clients must use v2",
+        "fix: handle empty input\n\nThe parser panicked.\n\nIt returns an error now.\n\nRefs: #12\nReviewed-by: Jane",
+        ":sparkles: feat(ui): add dark mode",
+        "feat(ui): ✨ add dark mode",
+        "fix: 🐛 handle empty input\n\nCloses: #42",
+    ];
+
+    #[test]
+    fn generate_round_trips_parsed_messages() {
+        for message in ROUND_TRIPS {
+            assert_eq!(parse(message).generate(), message);
+        }
+        // `Closes #42` is read as the `Closes` token and written with a colon
+        assert_eq!(parse("fix: x\n\nCloses #42").generate(), "fix: x\n\nCloses: #42");
+    }
+
+    #[test]
+    fn parses_header_parts() {
+        let commit = parse("feat(api)!: drop v1 endpoints");
+        assert_eq!(commit.commit_type, "feat");
+        assert_eq!(commit.scope.as_deref(), Some("api"));
+        assert_eq!(commit.description, "drop v1 endpoints");
+        assert!(commit.breaking_change);
+        assert_eq!(commit.breaking_style.marker, BreakingMarker::Bang);
+
+        assert_eq!(parse("fix(): x").scope, None);
+        assert!(ConventionalCommit::parse("Fix the parser").is_err());
+        assert!(ConventionalCommit::parse("fix:no space").is_err());
+        assert!(ConventionalCommit::parse("\n").is_err());
+    }
+
+    #[test]
+    fn parses_breaking_change_footers() {
+        let commit = parse("feat: x\n\nRefs: #1\nBREAKING-CHANGE: gone\n  for good");
+        assert!(commit.breaking_change);
+        assert_eq!(commit.breaking_change_description, "gone\n  for good");
+        assert_eq!(commit.breaking_style.marker, BreakingMarker::Footer);
+        assert_eq!(commit.breaking_style.token, "BREAKING-CHANGE");
+        assert_eq!(commit.footers, [Footer::new("Refs", "#1")]);
+
+        let commit = parse("feat!: x\n\nBREAKING CHANGE: gone");
+        assert_eq!(commit.breaking_style.marker, BreakingMarker::Both);
+
+        // Tokens are case sensitive and only read from the footer section
+        let commit = parse("feat: x\n\nBREAKING CHANGE: in the body\n\nmore body");
+        assert!(!commit.breaking_change);
+        assert!(!parse("feat: x\n\nBreaking-Change: y").breaking_change);
+    }
+
+    #[test]
+    fn parses_emoji_positions() {
+        let commit = parse(":sparkles: feat: x");
+        assert_eq!(
+            commit.emoji,
+            Some(Emoji { text: ":sparkles:".to_string(), position: EmojiPosition::Prefix })
+        );
+        assert_eq!(commit.description, "x");
+
+        let commit = parse("feat: 👩‍💻 x");
+        assert_eq!(
+            commit.emoji,
+            Some(Emoji { text: "👩‍💻".to_string(), position: EmojiPosition::Description })
+        );
+        assert_eq!(commit.description, "x");
+
+        assert_eq!(parse("feat: :) x").emoji, None);
+
+        // Text symbols are part of the description unless followed by U+FE0F
+        let commit = parse("fix: © notice in footer");
+        assert_eq!(commit.emoji, None);
+        assert_eq!(commit.description, "© notice in footer");
+        assert_eq!(parse("docs: ↔ arrows").emoji, None);
+        for emoji in ["♻️", "⚡️", "⬆️", "✅", "🚑️"] {
+            let commit = parse(&format!("{} fix: x", emoji));
+            assert_eq!(commit.emoji.map(|e| e.text).as_deref(), Some(emoji));
+        }
+    }
+
+    #[test]
+    fn generates_the_configured_breaking_marker() {
+        let mut commit = ConventionalCommit {
+            commit_type: "feat".to_string(),
+            description: "drop v1".to_string(),
+            breaking_change: true,
+            ..ConventionalCommit::default()
+        };
+        assert_eq!(commit.generate(), "feat!: drop v1");
+
+        commit.breaking_style.marker = BreakingMarker::Both;
+        assert_eq!(commit.generate(), "feat!: drop v1");
+        commit.breaking_style.marker = BreakingMarker::Footer;
+        assert_eq!(commit.generate(), "feat: drop v1\n\nBREAKING CHANGE: drop v1");
+
+        commit.breaking_change_description = "use v2".to_string();
+        commit.breaking_style.token = BREAKING_TOKENS[1];
+        assert_eq!(commit.generate(), "feat: drop v1\n\nBREAKING-CHANGE: use v2");
+        commit.breaking_style.marker = BreakingMarker::Bang;
+        assert_eq!(commit.generate(), "feat!: drop v1\n\nBREAKING-CHANGE: use v2");
+    }
+
+    #[test]
+    fn guesses_types_of_non_conventional_messages() {
+        let guess = |message: &str| {
+            let commit = ConventionalCommit::guess(message);
+            format!("{}: {}", commit.commit_type, commit.description)
+        };
+        assert_eq!(guess("Fixed crash on startup"), "fix: fixed crash on startup");
+        assert_eq!(guess("Added a login page"), "feat: added a login page");
+        assert_eq!(guess("Update README"), "docs: update README");
+        assert_eq!(guess("Bump serde"), "build: bump serde");
+        assert_eq!(guess("API cleanup"), "feat: API cleanup");
+        assert_eq!(guess("refactor(db): keep this"), "refactor: keep this");
+
+        let commit = ConventionalCommit::guess("Rename the module\n\nIt was confusing.");
+        assert_eq!(commit.commit_type, "refactor");
+        assert_eq!(commit.body.as_deref(), Some("It was confusing."));
+    }
+
+    #[test]
+    fn strips_comments_and_the_scissors_line() {
+        let message = "feat: x\n# comment\n\nbody\n# ------------------------ >8 ------------------------\ndiff";
+        assert_eq!(strip_comments(message, '#'), "feat: x\n\nbody");
+        assert_eq!(strip_comment_prefix("feat: x\n// note", "//"), "feat: x");
+        assert_eq!(strip_auto_comments("feat: x\n; note\n# other"), "feat: x");
+        assert!(is_exempt("Merge branch 'main'"));
+        assert!(!is_exempt("feat: x"));
+    }
+}
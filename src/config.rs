@@ -1,20 +1,746 @@
+use anyhow::{Context, Result};
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+use crate::commit::{BreakingMarker, BreakingStyle, ConventionalCommit, BREAKING_TOKENS};
+#[cfg(feature = "serde")]
+use crate::commit::EmojiPosition;
+#[cfg(feature = "serde")]
+use crate::emoji::EmojiFormat;
+use crate::emoji::{self, EmojiConfig};
+#[cfg(feature = "tui")]
+use crate::keymap::{self, Keymap};
+use crate::packages::{self, Package};
+use crate::preset::{self, Preset};
+use crate::suggest::{self, TypeRule};
+#[cfg(feature = "tui")]
+use crate::theme::{self, Theme};
+use toml::{Table, Value};
+
 pub const COMMIT_TYPES: [&str; 10] = [
     "feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore",
 ];
 
+/// Label of the first scope entry, meaning "no scope".
+pub const NO_SCOPE: &str = "<None>";
+
+/// Entries of the scope picker, starting with [`NO_SCOPE`]. The configured
+/// `scopes` are the entries after it.
 pub const COMMIT_SCOPES: [&str; 9] = [
-    "<None>", "ui", "api", "database", "auth", "config", "logging", "network", "storage",
+    NO_SCOPE, "ui", "api", "database", "auth", "config", "logging", "network", "storage",
 ];
 
-#[derive(Debug, Clone, Copy)]
+/// Built-in `(type, description, examples)` shown in the type picker.
+const TYPE_DOCS: [(&str, &str, &[&str]); 10] = [
+    ("feat", "A new feature", &["feat(auth): add login with GitHub"]),
+    ("fix", "A bug fix", &["fix(api): handle an empty pagination cursor"]),
+    ("docs", "Documentation only changes", &["docs: explain the config lookup order"]),
+    (
+        "style",
+        "Formatting and whitespace, without changing what the code does",
+        &["style: apply rustfmt"],
+    ),
+    (
+        "refactor",
+        "A code change that neither fixes a bug nor adds a feature",
+        &["refactor(database): extract the connection pool setup"],
+    ),
+    ("perf", "A code change that improves performance", &["perf(parser): avoid cloning tokens"]),
+    ("test", "Adding missing tests or correcting existing ones", &["test(api): cover rate limit errors"]),
+    (
+        "build",
+        "Changes to the build system or external dependencies",
+        &["build(deps): bump serde to 1.0.210", "build: enable LTO in the release profile"],
+    ),
+    (
+        "ci",
+        "Changes to CI configuration files and scripts",
+        &["ci: cache the cargo registry"],
+    ),
+    (
+        "chore",
+        "Maintenance that doesn't touch source or test files",
+        &["chore: ignore editor swap files"],
+    ),
+];
+
+/// Built-in `(scope, description)` shown in the scope picker.
+const SCOPE_DOCS: [(&str, &str); 8] = [
+    ("ui", "User interface and visual components"),
+    ("api", "Public or HTTP API"),
+    ("database", "Schema, queries and migrations"),
+    ("auth", "Authentication and authorization"),
+    ("config", "Configuration loading and defaults"),
+    ("logging", "Logging and tracing"),
+    ("network", "Networking and transport"),
+    ("storage", "File and object storage"),
+];
+
+/// Name of the per-repository configuration file, looked up at the work tree root.
+pub const CONFIG_FILE_NAME: &str = ".convinci.toml";
+
+/// Environment variable pointing to an explicit configuration file.
+pub const CONFIG_ENV_VAR: &str = "CONVINCI_CONFIG";
+
+/// The loaded configuration. With the `serde` feature it (de)serializes in
+/// the configuration file format, through the same parser as [`load_from`].
+#[derive(Debug, Clone)]
 pub struct AppConfig {
     pub dev_mode: bool,
+    /// Render the TUI inline below the prompt instead of in the alternate screen.
+    pub inline: bool,
+    /// Refuse description input that would make the header exceed `header-max-length`.
+    pub strict_header_length: bool,
+    pub types: Vec<String>,
+    pub scopes: Vec<String>,
+    /// Descriptions of types and scopes, keyed by name.
+    pub type_docs: BTreeMap<String, Doc>,
+    pub scope_docs: BTreeMap<String, Doc>,
+    pub rules: Rules,
+    pub presets: Vec<Preset>,
+    /// Packages of a monorepo, versioned separately. Empty for a single package.
+    pub packages: Vec<Package>,
+    /// Path prefixes per scope (`[scope-paths]` table), used to split commits.
+    pub scope_paths: BTreeMap<String, Vec<String>>,
+    /// Branches `reword` refuses to rewrite without `--force`.
+    pub protected_branches: Vec<String>,
+    /// Rules preselecting a type from the staged files, first match wins.
+    pub type_rules: Vec<TypeRule>,
+    /// Read from `[keys]`, but not serialized.
+    #[cfg(feature = "tui")]
+    pub keys: Keymap,
+    /// Read from `theme`, but not serialized.
+    #[cfg(feature = "tui")]
+    pub theme: Theme,
+    pub emoji: EmojiConfig,
+    pub breaking: BreakingStyle,
+    /// File the configuration was loaded from, `None` for built-in defaults.
+    pub source: Option<PathBuf>,
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
             dev_mode: false,
+            inline: false,
+            strict_header_length: false,
+            types: COMMIT_TYPES.iter().map(|t| t.to_string()).collect(),
+            scopes: COMMIT_SCOPES[1..].iter().map(|s| s.to_string()).collect(),
+            type_docs: TYPE_DOCS
+                .iter()
+                .map(|(name, description, examples)| {
+                    (name.to_string(), Doc::new(description, examples))
+                })
+                .collect(),
+            scope_docs: SCOPE_DOCS
+                .iter()
+                .map(|(name, description)| (name.to_string(), Doc::new(description, &[])))
+                .collect(),
+            rules: Rules::default(),
+            presets: Vec::new(),
+            packages: Vec::new(),
+            scope_paths: BTreeMap::new(),
+            protected_branches: vec!["main".to_string(), "master".to_string()],
+            type_rules: suggest::default_rules(),
+            #[cfg(feature = "tui")]
+            keys: Keymap::default(),
+            #[cfg(feature = "tui")]
+            theme: Theme::from_env(),
+            emoji: EmojiConfig::default(),
+            breaking: BreakingStyle::default(),
+            source: None,
+        }
+    }
+}
+
+impl AppConfig {
+    /// Applies the configured header style (emoji, breaking change marker)
+    /// before the commit is generated.
+    pub fn style_commit(&self, commit: &mut ConventionalCommit) {
+        self.emoji.decorate(commit);
+        commit.breaking_style = self.breaking;
+    }
+
+    /// The configured packages, or the whole repository as a single one.
+    pub fn release_packages(&self) -> Vec<Package> {
+        if self.packages.is_empty() {
+            vec![Package::root()]
+        } else {
+            self.packages.clone()
+        }
+    }
+
+    /// The scope of a file from `[scope-paths]` and the package directories.
+    /// The longest matching prefix wins.
+    pub fn scope_for_path(&self, file: &str) -> Option<&str> {
+        let configured = self
+            .scope_paths
+            .iter()
+            .flat_map(|(scope, paths)| paths.iter().map(move |path| (scope.as_str(), Path::new(path))));
+        let packages = self
+            .packages
+            .iter()
+            .filter_map(|package| Some((package.scopes.first()?.as_str(), package.path.as_path())));
+
+        configured
+            .chain(packages)
+            .filter(|(_, path)| Path::new(file).starts_with(path))
+            .max_by_key(|(_, path)| path.components().count())
+            .map(|(scope, _)| scope)
+    }
+
+    pub fn preset(&self, name: &str) -> Result<&Preset> {
+        self.presets.iter().find(|p| p.name == name).with_context(|| {
+            let names: Vec<&str> = self.presets.iter().map(|p| p.name.as_str()).collect();
+            if names.is_empty() {
+                format!("Unknown preset `{}`, no presets are configured", name)
+            } else {
+                format!("Unknown preset `{}`. Available: {}", name, names.join(", "))
+            }
+        })
+    }
+}
+
+/// What a type or scope is for, shown in the pickers.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Doc {
+    pub description: String,
+    pub examples: Vec<String>,
+}
+
+impl Doc {
+    fn new(description: &str, examples: &[&str]) -> Self {
+        Self {
+            description: description.to_string(),
+            examples: examples.iter().map(|e| e.to_string()).collect(),
+        }
+    }
+}
+
+/// Validation rules applied to commit messages (`[rules]` table). All of
+/// them are off by default, leaving only the Conventional Commits format.
+#[derive(Debug, Clone, Default)]
+pub struct Rules {
+    /// Maximum length of the header line, `None` disables the check.
+    pub header_max_length: Option<usize>,
+    /// Maximum length of each body line, `None` disables the check.
+    pub body_max_line_length: Option<usize>,
+    /// Only accept types listed in `types`.
+    pub type_enum: bool,
+    /// Only accept scopes listed in `scopes`.
+    pub scope_enum: bool,
+    /// Reject commits without a scope.
+    pub scope_required: bool,
+    /// Reject breaking changes without a `BREAKING CHANGE` footer.
+    pub breaking_description_required: bool,
+    /// Reject descriptions starting with `added` or `adds` instead of `add`.
+    pub description_imperative: bool,
+    /// Reject descriptions ending with a period.
+    pub description_no_period: bool,
+    /// Case of the first letter of the description, `None` accepts both.
+    pub description_case: Option<LetterCase>,
+    /// Words and phrases rejected in descriptions, ignoring case.
+    pub banned_words: Vec<String>,
+    /// Check the spelling of descriptions against the bundled dictionary.
+    pub spell_check: bool,
+    /// Project words accepted by the spell check, besides types and scopes.
+    pub words: Vec<String>,
+}
+
+/// Case required for the first letter of a description.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LetterCase {
+    Lower,
+    Upper,
+}
+
+impl LetterCase {
+    fn name(self) -> &'static str {
+        match self {
+            LetterCase::Lower => "lower",
+            LetterCase::Upper => "upper",
+        }
+    }
+}
+
+impl Rules {
+    /// Human readable `(rule, setting)` pairs, used by `convinci doctor`.
+    pub fn summary(&self) -> Vec<(&'static str, String)> {
+        let limit = |value: Option<usize>| match value {
+            Some(max) => max.to_string(),
+            None => "off".to_string(),
+        };
+        let toggle = |value: bool| if value { "on" } else { "off" }.to_string();
+        let list = |values: &[String]| if values.is_empty() { "none".to_string() } else { values.join(", ") };
+
+        vec![
+            ("header-max-length", limit(self.header_max_length)),
+            ("body-max-line-length", limit(self.body_max_line_length)),
+            ("type-enum", toggle(self.type_enum)),
+            ("scope-enum", toggle(self.scope_enum)),
+            ("scope-required", toggle(self.scope_required)),
+            ("breaking-description-required", toggle(self.breaking_description_required)),
+            ("description-imperative", toggle(self.description_imperative)),
+            ("description-no-period", toggle(self.description_no_period)),
+            (
+                "description-case",
+                self.description_case.map_or("off", LetterCase::name).to_string(),
+            ),
+            ("banned-words", list(&self.banned_words)),
+            ("spell-check", toggle(self.spell_check)),
+            ("words", list(&self.words)),
+        ]
+    }
+}
+
+/// Where a configuration file candidate comes from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfigOrigin {
+    Env,
+    Repository,
+    User,
+}
+
+impl ConfigOrigin {
+    pub fn label(self) -> &'static str {
+        match self {
+            ConfigOrigin::Env => CONFIG_ENV_VAR,
+            ConfigOrigin::Repository => "repository",
+            ConfigOrigin::User => "user",
+        }
+    }
+}
+
+/// Configuration files in lookup order. The first one that exists wins.
+pub fn candidate_paths() -> Vec<(ConfigOrigin, PathBuf)> {
+    let mut candidates = Vec::new();
+
+    if let Some(path) = env::var_os(CONFIG_ENV_VAR).filter(|p| !p.is_empty()) {
+        candidates.push((ConfigOrigin::Env, PathBuf::from(path)));
+    }
+    if let Some(root) = crate::git::work_tree_root() {
+        candidates.push((ConfigOrigin::Repository, root.join(CONFIG_FILE_NAME)));
+    }
+    if let Some(dir) = user_config_dir() {
+        candidates.push((ConfigOrigin::User, dir.join("convinci").join("config.toml")));
+    }
+
+    candidates
+}
+
+/// Platform configuration directory (`$XDG_CONFIG_HOME`, `~/.config` or `%APPDATA%`).
+pub fn user_config_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        return env::var_os("APPDATA").map(PathBuf::from);
+    }
+    env::var_os("XDG_CONFIG_HOME")
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+}
+
+/// Platform data directory (`$XDG_DATA_HOME`, `~/.local/share` or `%LOCALAPPDATA%`).
+pub fn user_data_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        return env::var_os("LOCALAPPDATA")
+            .or_else(|| env::var_os("APPDATA"))
+            .map(PathBuf::from);
+    }
+    env::var_os("XDG_DATA_HOME")
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+}
+
+/// Loads the first configuration file found in [`candidate_paths`], or the defaults.
+pub fn load() -> Result<AppConfig> {
+    for (origin, path) in candidate_paths() {
+        if path.is_file() {
+            return load_from(&path);
+        }
+        if origin == ConfigOrigin::Env {
+            anyhow::bail!(
+                "{} points to a missing file: {}",
+                CONFIG_ENV_VAR,
+                path.display()
+            );
+        }
+    }
+    Ok(AppConfig::default())
+}
+
+pub fn load_from(path: &Path) -> Result<AppConfig> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file {}", path.display()))?;
+    let table: Table = content
+        .parse()
+        .with_context(|| format!("Invalid TOML in {}", path.display()))?;
+
+    let mut config = parse_table(&table)
+        .with_context(|| format!("Invalid configuration in {}", path.display()))?;
+    config.source = Some(path.to_path_buf());
+    Ok(config)
+}
+
+pub(crate) fn parse_table(table: &Table) -> Result<AppConfig> {
+    let mut config = AppConfig::default();
+
+    if let Some(types) = table.get("types") {
+        config.types = documented_list(types, "types", &mut config.type_docs)?;
+    }
+    if let Some(scopes) = table.get("scopes") {
+        config.scopes = documented_list(scopes, "scopes", &mut config.scope_docs)?;
+    }
+    if let Some(inline) = table.get("inline") {
+        config.inline = boolean(inline, "inline")?;
+    }
+    if let Some(strict) = table.get("strict-header-length") {
+        config.strict_header_length = boolean(strict, "strict-header-length")?;
+    }
+    if let Some(branches) = table.get("protected-branches") {
+        config.protected_branches = string_list(branches, "protected-branches")?;
+    }
+    if let Some(rules) = table.get("rules") {
+        let rules = rules.as_table().context("`rules` must be a table")?;
+        parse_rules(rules, &mut config.rules)?;
+    }
+    if let Some(presets) = table.get("presets") {
+        let presets = presets.as_table().context("`presets` must be a table")?;
+        config.presets = preset::parse(presets)?;
+    }
+    if let Some(value) = table.get("packages") {
+        let value = value.as_table().context("`packages` must be a table")?;
+        config.packages = packages::parse(value)?;
+    }
+    if let Some(value) = table.get("type-rules") {
+        config.type_rules = suggest::parse(value)?;
+    }
+    if let Some(value) = table.get("scope-paths") {
+        let value = value.as_table().context("`scope-paths` must be a table")?;
+        for (scope, paths) in value {
+            let key = format!("scope-paths.{}", scope);
+            let paths = match paths {
+                Value::String(path) => vec![path.clone()],
+                _ => string_list(paths, &key)?,
+            };
+            let paths = paths
+                .iter()
+                .map(|p| packages::normalize(p).to_string_lossy().into_owned())
+                .collect();
+            config.scope_paths.insert(scope.clone(), paths);
+        }
+    }
+    // Without the TUI these are accepted but not interpreted
+    #[cfg(feature = "tui")]
+    if let Some(keys) = table.get("keys") {
+        let keys = keys.as_table().context("`keys` must be a table")?;
+        config.keys = keymap::parse(keys)?;
+    }
+    #[cfg(feature = "tui")]
+    if let Some(value) = table.get("theme") {
+        config.theme = theme::parse(value).context("Invalid `theme`")?;
+    }
+    if let Some(value) = table.get("breaking") {
+        let value = value.as_table().context("`breaking` must be a table")?;
+        config.breaking = parse_breaking(value)?;
+    }
+    if let Some(value) = table.get("emoji") {
+        let value = value.as_table().context("`emoji` must be a table")?;
+        config.emoji = emoji::parse(value)?;
+    }
+
+    if config.types.is_empty() {
+        anyhow::bail!("`types` must contain at least one commit type");
+    }
+    Ok(config)
+}
+
+fn parse_rules(table: &Table, rules: &mut Rules) -> Result<()> {
+    for (key, value) in table {
+        match key.as_str() {
+            "header-max-length" => rules.header_max_length = limit(value, key)?,
+            "body-max-line-length" => rules.body_max_line_length = limit(value, key)?,
+            "type-enum" => rules.type_enum = boolean(value, key)?,
+            "scope-enum" => rules.scope_enum = boolean(value, key)?,
+            "scope-required" => rules.scope_required = boolean(value, key)?,
+            "breaking-description-required" => {
+                rules.breaking_description_required = boolean(value, key)?
+            }
+            "description-imperative" => rules.description_imperative = boolean(value, key)?,
+            "description-no-period" => rules.description_no_period = boolean(value, key)?,
+            "description-case" => {
+                rules.description_case = match value {
+                    Value::String(case) if case == "lower" => Some(LetterCase::Lower),
+                    Value::String(case) if case == "upper" => Some(LetterCase::Upper),
+                    Value::Boolean(false) => None,
+                    _ => anyhow::bail!("`{}` must be \"lower\", \"upper\" or false", key),
+                }
+            }
+            "banned-words" => rules.banned_words = string_list(value, key)?,
+            "spell-check" => rules.spell_check = boolean(value, key)?,
+            "words" => rules.words = string_list(value, key)?,
+            _ => anyhow::bail!("Unknown rule `{}`", key),
+        }
+    }
+    Ok(())
+}
+
+/// A list of names, where each entry is either a string or a table such as
+/// `{ name = "deps", description = "...", examples = ["..."] }`.
+fn documented_list(value: &Value, key: &str, docs: &mut BTreeMap<String, Doc>) -> Result<Vec<String>> {
+    let array = value
+        .as_array()
+        .with_context(|| format!("`{}` must be an array", key))?;
+
+    array
+        .iter()
+        .map(|item| match item {
+            Value::String(name) => Ok(name.clone()),
+            Value::Table(entry) => {
+                let name = entry
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .with_context(|| format!("Entries of `{}` need a `name`", key))?
+                    .to_string();
+                let mut doc = Doc::default();
+                for (field, value) in entry {
+                    match field.as_str() {
+                        "name" => {}
+                        "description" => {
+                            doc.description = value
+                                .as_str()
+                                .with_context(|| format!("`description` of `{}` must be a string", name))?
+                                .to_string();
+                        }
+                        "examples" => doc.examples = string_list(value, "examples")?,
+                        _ => anyhow::bail!("Unknown key `{}` in `{}` entry `{}`", field, key, name),
+                    }
+                }
+                docs.insert(name.clone(), doc);
+                Ok(name)
+            }
+            _ => anyhow::bail!("`{}` must contain strings or tables", key),
+        })
+        .collect()
+}
+
+fn parse_breaking(table: &Table) -> Result<BreakingStyle> {
+    let mut style = BreakingStyle::default();
+    for (key, value) in table {
+        match key.as_str() {
+            "style" => {
+                style.marker = match value.as_str() {
+                    Some("bang") => BreakingMarker::Bang,
+                    Some("footer") => BreakingMarker::Footer,
+                    Some("both") => BreakingMarker::Both,
+                    _ => anyhow::bail!("`breaking.style` must be \"bang\", \"footer\" or \"both\""),
+                }
+            }
+            "token" => {
+                style.token = BREAKING_TOKENS
+                    .into_iter()
+                    .find(|t| Some(*t) == value.as_str())
+                    .with_context(|| {
+                        format!("`breaking.token` must be one of: {}", BREAKING_TOKENS.join(", "))
+                    })?;
+            }
+            _ => anyhow::bail!("Unknown key `breaking.{}`", key),
+        }
+    }
+    Ok(style)
+}
+
+pub(crate) fn string_list(value: &Value, key: &str) -> Result<Vec<String>> {
+    let array = value
+        .as_array()
+        .with_context(|| format!("`{}` must be an array of strings", key))?;
+    array
+        .iter()
+        .map(|item| {
+            item.as_str()
+                .map(str::to_string)
+                .with_context(|| format!("`{}` must be an array of strings", key))
+        })
+        .collect()
+}
+
+pub(crate) fn boolean(value: &Value, key: &str) -> Result<bool> {
+    value
+        .as_bool()
+        .with_context(|| format!("`{}` must be true or false", key))
+}
+
+/// A positive integer limit; `0` or `false` disables it.
+fn limit(value: &Value, key: &str) -> Result<Option<usize>> {
+    match value {
+        Value::Integer(n) if *n > 0 => Ok(Some(*n as usize)),
+        Value::Integer(0) | Value::Boolean(false) => Ok(None),
+        _ => anyhow::bail!("`{}` must be a positive integer or false", key),
+    }
+}
+
+/// The configuration in the file format, for [`serde::Serialize`]. Key
+/// bindings, the theme and the source path are left out.
+#[cfg(feature = "serde")]
+fn config_table(config: &AppConfig) -> Table {
+    let strings = |values: &[String]| Value::Array(values.iter().cloned().map(Value::String).collect());
+    let documented = |names: &[String], docs: &BTreeMap<String, Doc>| {
+        let entries = names.iter().map(|name| match docs.get(name) {
+            Some(doc) => Value::Table(Table::from_iter([
+                ("name".to_string(), Value::String(name.clone())),
+                ("description".to_string(), Value::String(doc.description.clone())),
+                ("examples".to_string(), strings(&doc.examples)),
+            ])),
+            None => Value::String(name.clone()),
+        });
+        Value::Array(entries.collect())
+    };
+
+    let mut table = Table::new();
+    table.insert("inline".into(), Value::Boolean(config.inline));
+    table.insert("strict-header-length".into(), Value::Boolean(config.strict_header_length));
+    table.insert("types".into(), documented(&config.types, &config.type_docs));
+    table.insert("scopes".into(), documented(&config.scopes, &config.scope_docs));
+    table.insert("protected-branches".into(), strings(&config.protected_branches));
+    table.insert("rules".into(), Value::Table(rules_table(&config.rules)));
+
+    let presets = config.presets.iter().map(|preset| {
+        let mut entry = Table::new();
+        entry.insert("type".into(), Value::String(preset.commit_type.clone()));
+        if let Some(scope) = &preset.scope {
+            entry.insert("scope".into(), Value::String(scope.clone()));
+        }
+        entry.insert("description".into(), Value::String(preset.description.clone()));
+        if let Some(body) = &preset.body {
+            entry.insert("body".into(), Value::String(body.clone()));
+        }
+        (preset.name.clone(), Value::Table(entry))
+    });
+    table.insert("presets".into(), Value::Table(presets.collect()));
+
+    let packages = config.packages.iter().map(|package| {
+        let mut entry = Table::new();
+        entry.insert("path".into(), Value::String(package.path.to_string_lossy().into_owned()));
+        entry.insert("scopes".into(), strings(&package.scopes));
+        if let Some(dependencies) = &package.dependencies {
+            entry.insert("dependencies".into(), strings(dependencies));
         }
+        (package.name.clone(), Value::Table(entry))
+    });
+    table.insert("packages".into(), Value::Table(packages.collect()));
+
+    let type_rules = config.type_rules.iter().map(|rule| {
+        let mut entry = Table::new();
+        entry.insert("type".into(), Value::String(rule.commit_type.clone()));
+        if let Some(scope) = &rule.scope {
+            entry.insert("scope".into(), Value::String(scope.clone()));
+        }
+        entry.insert("paths".into(), strings(&rule.paths));
+        Value::Table(entry)
+    });
+    table.insert("type-rules".into(), Value::Array(type_rules.collect()));
+
+    let scope_paths = config
+        .scope_paths
+        .iter()
+        .map(|(scope, paths)| (scope.clone(), strings(paths)));
+    table.insert("scope-paths".into(), Value::Table(scope_paths.collect()));
+
+    let style = match config.breaking.marker {
+        BreakingMarker::Bang => "bang",
+        BreakingMarker::Footer => "footer",
+        BreakingMarker::Both => "both",
+    };
+    table.insert(
+        "breaking".into(),
+        Value::Table(Table::from_iter([
+            ("style".to_string(), Value::String(style.into())),
+            ("token".to_string(), Value::String(config.breaking.token.into())),
+        ])),
+    );
+
+    let emoji = &config.emoji;
+    let position = match emoji.position {
+        EmojiPosition::Prefix => "prefix",
+        EmojiPosition::Description => "description",
+    };
+    let format = match emoji.format {
+        EmojiFormat::Unicode => "unicode",
+        EmojiFormat::Shortcode => "shortcode",
+    };
+    let types = emoji.types.iter().map(|(t, e)| (t.clone(), Value::String(e.clone())));
+    table.insert(
+        "emoji".into(),
+        Value::Table(Table::from_iter([
+            ("enabled".to_string(), Value::Boolean(emoji.enabled)),
+            ("position".to_string(), Value::String(position.into())),
+            ("format".to_string(), Value::String(format.into())),
+            ("types".to_string(), Value::Table(types.collect())),
+        ])),
+    );
+
+    table
+}
+
+/// The `[rules]` table, with `false` for the checks that are off.
+#[cfg(feature = "serde")]
+fn rules_table(rules: &Rules) -> Table {
+    let limit = |value: Option<usize>| value.map_or(Value::Boolean(false), |max| Value::Integer(max as i64));
+    let strings = |values: &[String]| Value::Array(values.iter().cloned().map(Value::String).collect());
+    let case = rules
+        .description_case
+        .map_or(Value::Boolean(false), |case| Value::String(case.name().into()));
+
+    Table::from_iter([
+        ("header-max-length".to_string(), limit(rules.header_max_length)),
+        ("body-max-line-length".to_string(), limit(rules.body_max_line_length)),
+        ("type-enum".to_string(), Value::Boolean(rules.type_enum)),
+        ("scope-enum".to_string(), Value::Boolean(rules.scope_enum)),
+        ("scope-required".to_string(), Value::Boolean(rules.scope_required)),
+        (
+            "breaking-description-required".to_string(),
+            Value::Boolean(rules.breaking_description_required),
+        ),
+        ("description-imperative".to_string(), Value::Boolean(rules.description_imperative)),
+        ("description-no-period".to_string(), Value::Boolean(rules.description_no_period)),
+        ("description-case".to_string(), case),
+        ("banned-words".to_string(), strings(&rules.banned_words)),
+        ("spell-check".to_string(), Value::Boolean(rules.spell_check)),
+        ("words".to_string(), strings(&rules.words)),
+    ])
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for AppConfig {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        config_table(self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AppConfig {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let table = Table::deserialize(deserializer)?;
+        parse_table(&table).map_err(|e| serde::de::Error::custom(format!("{:#}", e)))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Rules {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        rules_table(self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Rules {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let table = Table::deserialize(deserializer)?;
+        let mut rules = Rules::default();
+        parse_rules(&table, &mut rules).map_err(|e| serde::de::Error::custom(format!("{:#}", e)))?;
+        Ok(rules)
     }
 }
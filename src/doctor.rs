@@ -0,0 +1,80 @@
+use crate::{config, git, hooks, keymap::Action};
+use anyhow::Result;
+
+/// Reports the health of the Convinci setup for the current repository.
+pub fn run() -> Result<()> {
+    println!("Convinci {}", env!("CARGO_PKG_VERSION"));
+    println!();
+
+    println!("Git");
+    match git::version() {
+        Ok(version) => println!("✅ {}", version),
+        Err(_) => println!("❌ git not found on PATH"),
+    }
+    let in_repo = git::is_inside_work_tree();
+    if !in_repo {
+        println!("⚠️  not inside a Git repository");
+    }
+    println!();
+
+    println!("Hooks");
+    if in_repo {
+        hooks::print_status()?;
+    } else {
+        println!("   skipped (not a repository)");
+    }
+    println!();
+
+    println!("Configuration");
+    let mut resolved = false;
+    for (origin, path) in config::candidate_paths() {
+        let marker = if resolved {
+            "  "
+        } else if path.is_file() {
+            resolved = true;
+            "→ "
+        } else {
+            "  "
+        };
+        let state = if path.is_file() { "found" } else { "missing" };
+        println!(
+            "{}{:<11} {} ({})",
+            marker,
+            origin.label(),
+            path.display(),
+            state
+        );
+    }
+    println!(
+        "{}{:<11} built-in defaults",
+        if resolved { "  " } else { "→ " },
+        "default"
+    );
+
+    let config = match config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            println!("❌ {:#}", e);
+            return Ok(());
+        }
+    };
+    println!();
+
+    println!("Rules");
+    for (rule, setting) in config.rules.summary() {
+        println!("   {:<22} {}", rule, setting);
+    }
+    println!("   types: {}", config.types.join(", "));
+    println!("   scopes: {}", config.scopes.join(", "));
+    println!("   theme: {}", config.theme.name);
+    println!();
+
+    println!("Keys");
+    for action in Action::ALL {
+        let keys: Vec<String> = config.keys.keys(action).iter().map(|k| k.to_string()).collect();
+        let keys = if keys.is_empty() { "unbound".to_string() } else { keys.join(", ") };
+        println!("   {:<22} {}", action.name(), keys);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use crate::{
+    commit::{BreakingMarker, ConventionalCommit, Emoji, EmojiPosition, Footer, BREAKING_TOKENS},
+    git,
+};
+use anyhow::{Context, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// File in the git directory holding the autosaved draft, so drafts are per repository.
+pub const DRAFT_FILE: &str = "CONVINCI_DRAFT";
+
+const BODY_MARKER: &str = "body:";
+
+/// Location of the draft for the current repository, if there is one.
+pub fn path() -> Option<PathBuf> {
+    if !git::is_inside_work_tree() {
+        return None;
+    }
+    git::git_path(DRAFT_FILE).ok()
+}
+
+/// Whether the commit has any text worth keeping.
+pub fn is_empty(commit: &ConventionalCommit) -> bool {
+    commit.description.trim().is_empty()
+        && commit.body.as_deref().is_none_or(|b| b.trim().is_empty())
+        && commit.breaking_change_description.trim().is_empty()
+}
+
+/// Serializes every field, including ones `generate` would drop (e.g. an empty description).
+pub fn serialize(commit: &ConventionalCommit) -> String {
+    let mut out = format!(
+        "type: {}\nscope: {}\nbreaking: {}\nbreaking-description: {}\ndescription: {}\n",
+        commit.commit_type,
+        commit.scope.as_deref().unwrap_or_default(),
+        commit.breaking_change,
+        commit.breaking_change_description.replace('\n', "\\n"),
+        commit.description,
+    );
+    if let Some(emoji) = &commit.emoji {
+        let position = match emoji.position {
+            EmojiPosition::Prefix => "prefix",
+            EmojiPosition::Description => "description",
+        };
+        out.push_str(&format!("emoji: {} {}\n", position, emoji.text));
+    }
+    let marker = match commit.breaking_style.marker {
+        BreakingMarker::Bang => "bang",
+        BreakingMarker::Footer => "footer",
+        BreakingMarker::Both => "both",
+    };
+    out.push_str(&format!(
+        "breaking-style: {} {}\n",
+        marker, commit.breaking_style.token
+    ));
+    for footer in &commit.footers {
+        out.push_str(&format!(
+            "footer: {}: {}\n",
+            footer.token,
+            footer.value.replace('\n', "\\n")
+        ));
+    }
+    out.push_str(BODY_MARKER);
+    out.push('\n');
+    if let Some(body) = &commit.body {
+        out.push_str(body);
+    }
+    out
+}
+
+/// Lines it can't make sense of are skipped, so a damaged draft keeps what it can.
+pub fn deserialize(content: &str) -> Option<ConventionalCommit> {
+    let mut commit = ConventionalCommit::default();
+    let mut lines = content.lines();
+
+    for line in lines.by_ref() {
+        if line == BODY_MARKER {
+            break;
+        }
+        let Some((key, value)) = line.split_once(": ").or_else(|| line.split_once(':')) else {
+            continue;
+        };
+        match key {
+            "type" => commit.commit_type = value.to_string(),
+            "scope" => commit.scope = Some(value.to_string()).filter(|s| !s.is_empty()),
+            "breaking" => commit.breaking_change = value == "true",
+            "breaking-description" => commit.breaking_change_description = value.replace("\\n", "\n"),
+            "description" => commit.description = value.to_string(),
+            "footer" => {
+                if let Some((token, value)) = value.split_once(": ") {
+                    commit.footers.push(Footer::new(token, value.replace("\\n", "\n")));
+                }
+            }
+            "emoji" => {
+                let position = match value.split_once(' ') {
+                    Some(("prefix", text)) => Some((EmojiPosition::Prefix, text)),
+                    Some(("description", text)) => Some((EmojiPosition::Description, text)),
+                    _ => None,
+                };
+                commit.emoji = position
+                    .filter(|(_, text)| !text.is_empty())
+                    .map(|(position, text)| Emoji { text: text.to_string(), position });
+            }
+            "breaking-style" => {
+                let (marker, token) = value.split_once(' ').unwrap_or((value, ""));
+                match marker {
+                    "bang" => commit.breaking_style.marker = BreakingMarker::Bang,
+                    "footer" => commit.breaking_style.marker = BreakingMarker::Footer,
+                    "both" => commit.breaking_style.marker = BreakingMarker::Both,
+                    _ => {}
+                }
+                if let Some(token) = BREAKING_TOKENS.into_iter().find(|t| *t == token) {
+                    commit.breaking_style.token = token;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let body = lines.collect::<Vec<_>>().join("\n");
+    commit.body = Some(body).filter(|b| !b.is_empty());
+
+    Some(commit).filter(|c| !c.commit_type.is_empty() && !is_empty(c))
+}
+
+pub fn load(path: &Path) -> Option<ConventionalCommit> {
+    fs::read_to_string(path).ok().and_then(|content| deserialize(&content))
+}
+
+/// Writes the draft, or removes it once the form is empty again.
+pub fn save(path: &Path, commit: &ConventionalCommit) -> Result<()> {
+    if is_empty(commit) {
+        discard(path);
+        return Ok(());
+    }
+    fs::write(path, serialize(commit))
+        .with_context(|| format!("Failed to save draft to {}", path.display()))
+}
+
+pub fn discard(path: &Path) {
+    let _ = fs::remove_file(path);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commit::{BreakingStyle, BREAKING_TOKENS};
+
+    #[test]
+    fn round_trips_every_field() {
+        let commit = ConventionalCommit {
+            commit_type: "feat".to_string(),
+            scope: Some("api".to_string()),
+            description: "drop v1".to_string(),
+            body: Some("First paragraph.\n\nSecond: with a colon\nand more lines".to_string()),
+            breaking_change: true,
+            breaking_change_description: "clients must use v2\n  see the guide".to_string(),
+            footers: vec![Footer::new("Refs", "#12"), Footer::new("Note", "two\nlines")],
+            emoji: Some(Emoji { text: "💥".to_string(), position: EmojiPosition::Prefix }),
+            breaking_style: BreakingStyle { marker: BreakingMarker::Both, token: BREAKING_TOKENS[1] },
+        };
+        assert_eq!(deserialize(&serialize(&commit)), Some(commit));
+    }
+
+    #[test]
+    fn skips_unreadable_lines() {
+        let draft = "type: fix\nnot a field\nemoji: sideways 🙃\nbreaking-style: loud\ndescription: keep me\nbody:\n";
+        let commit = deserialize(draft).unwrap();
+        assert_eq!(commit.commit_type, "fix");
+        assert_eq!(commit.description, "keep me");
+        assert_eq!(commit.emoji, None);
+        assert_eq!(commit.breaking_style, BreakingStyle::default());
+    }
+
+    #[test]
+    fn ignores_empty_drafts() {
+        assert!(deserialize("type: feat\ndescription: \nbody:\n").is_none());
+        assert!(deserialize("").is_none());
+    }
+}
@@ -0,0 +1,167 @@
+use crate::commit::{ConventionalCommit, Emoji, EmojiPosition};
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use toml::{Table, Value};
+
+/// Gitmoji shortcodes and their unicode form, used to convert between the
+/// two. The first ten are the defaults for the built-in types.
+const GITMOJI: [(&str, &str); 26] = [
+    ("sparkles", "✨"),
+    ("bug", "🐛"),
+    ("memo", "📝"),
+    ("art", "🎨"),
+    ("recycle", "♻️"),
+    ("zap", "⚡️"),
+    ("white_check_mark", "✅"),
+    ("package", "📦"),
+    ("construction_worker", "👷"),
+    ("wrench", "🔧"),
+    ("boom", "💥"),
+    ("fire", "🔥"),
+    ("ambulance", "🚑️"),
+    ("lipstick", "💄"),
+    ("lock", "🔒️"),
+    ("rocket", "🚀"),
+    ("tada", "🎉"),
+    ("bookmark", "🔖"),
+    ("rotating_light", "🚨"),
+    ("arrow_up", "⬆️"),
+    ("arrow_down", "⬇️"),
+    ("heavy_plus_sign", "➕"),
+    ("heavy_minus_sign", "➖"),
+    ("rewind", "⏪️"),
+    ("truck", "🚚"),
+    ("green_heart", "💚"),
+];
+
+const DEFAULT_TYPES: [(&str, &str); 10] = [
+    ("feat", "sparkles"),
+    ("fix", "bug"),
+    ("docs", "memo"),
+    ("style", "art"),
+    ("refactor", "recycle"),
+    ("perf", "zap"),
+    ("test", "white_check_mark"),
+    ("build", "package"),
+    ("ci", "construction_worker"),
+    ("chore", "wrench"),
+];
+
+/// How emojis are written in the header.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum EmojiFormat {
+    /// `✨`
+    Unicode,
+    /// `:sparkles:`
+    Shortcode,
+}
+
+/// Gitmoji mode (`[emoji]` table).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct EmojiConfig {
+    pub enabled: bool,
+    pub position: EmojiPosition,
+    pub format: EmojiFormat,
+    /// Emoji per commit type, as a shortcode without colons or as unicode.
+    pub types: BTreeMap<String, String>,
+}
+
+impl Default for EmojiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            position: EmojiPosition::Description,
+            format: EmojiFormat::Unicode,
+            types: DEFAULT_TYPES
+                .iter()
+                .map(|(t, e)| (t.to_string(), e.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl EmojiConfig {
+    /// The emoji for a commit type in the configured format.
+    pub fn for_type(&self, commit_type: &str) -> Option<String> {
+        let emoji = self.types.get(commit_type)?;
+        Some(match self.format {
+            EmojiFormat::Unicode => GITMOJI
+                .iter()
+                .find(|(code, _)| code == emoji)
+                .map(|(_, unicode)| unicode.to_string())
+                // Unknown shortcodes still render on forges like GitHub
+                .unwrap_or_else(|| shortcode_or_unicode(emoji)),
+            EmojiFormat::Shortcode => GITMOJI
+                .iter()
+                .find(|(_, unicode)| unicode.trim_end_matches('\u{FE0F}') == emoji.trim_end_matches('\u{FE0F}'))
+                .map(|(code, _)| format!(":{}:", code))
+                .unwrap_or_else(|| shortcode_or_unicode(emoji)),
+        })
+    }
+
+    /// Sets the emoji of the commit from its type when the mode is enabled.
+    pub fn decorate(&self, commit: &mut ConventionalCommit) {
+        if !self.enabled {
+            return;
+        }
+        commit.emoji = self.for_type(&commit.commit_type).map(|text| Emoji {
+            text,
+            position: self.position,
+        });
+    }
+}
+
+/// Names are stored without colons; anything else is unicode and kept as is.
+fn shortcode_or_unicode(emoji: &str) -> String {
+    if emoji.chars().all(|c| c.is_ascii_alphanumeric() || "_+-".contains(c)) {
+        format!(":{}:", emoji)
+    } else {
+        emoji.to_string()
+    }
+}
+
+/// Parses the `[emoji]` table. Having the table enables the mode unless
+/// `enabled = false` is given.
+pub fn parse(table: &Table) -> Result<EmojiConfig> {
+    let mut config = EmojiConfig {
+        enabled: true,
+        ..EmojiConfig::default()
+    };
+
+    for (key, value) in table {
+        match key.as_str() {
+            "enabled" => config.enabled = crate::config::boolean(value, "emoji.enabled")?,
+            "position" => {
+                config.position = match value.as_str() {
+                    Some("prefix") => EmojiPosition::Prefix,
+                    Some("description") => EmojiPosition::Description,
+                    _ => anyhow::bail!("`emoji.position` must be \"prefix\" or \"description\""),
+                }
+            }
+            "format" => {
+                config.format = match value.as_str() {
+                    Some("unicode") => EmojiFormat::Unicode,
+                    Some("shortcode") => EmojiFormat::Shortcode,
+                    _ => anyhow::bail!("`emoji.format` must be \"unicode\" or \"shortcode\""),
+                }
+            }
+            "types" => {
+                let types = value.as_table().context("`emoji.types` must be a table")?;
+                for (commit_type, emoji) in types {
+                    let emoji = match emoji {
+                        Value::String(e) if !e.trim().is_empty() => e.trim().trim_matches(':'),
+                        _ => anyhow::bail!("`emoji.types.{}` must be an emoji", commit_type),
+                    };
+                    config.types.insert(commit_type.clone(), emoji.to_string());
+                }
+            }
+            _ => anyhow::bail!("Unknown key `emoji.{}`", key),
+        }
+    }
+
+    Ok(config)
+}
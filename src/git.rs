@@ -0,0 +1,125 @@
+use anyhow::{Context, Result};
+use std::{path::PathBuf, process::Command};
+
+/// File in the git directory holding the message of the commit in progress.
+pub const MESSAGE_FILE: &str = "CONVINCI_MSG";
+
+/// Flags forwarded to `git commit`.
+#[derive(Debug, Default, Clone)]
+pub struct CommitOptions {
+    pub amend: bool,
+    pub signoff: bool,
+    pub gpg_sign: bool,
+    pub no_verify: bool,
+    pub allow_empty: bool,
+    /// Arbitrary arguments given after `--` on the command line.
+    pub extra_args: Vec<String>,
+}
+
+impl CommitOptions {
+    pub fn args(&self) -> Vec<String> {
+        let flags = [
+            (self.amend, "--amend"),
+            (self.signoff, "--signoff"),
+            (self.gpg_sign, "--gpg-sign"),
+            (self.no_verify, "--no-verify"),
+            (self.allow_empty, "--allow-empty"),
+        ];
+
+        flags
+            .iter()
+            .filter(|(enabled, _)| *enabled)
+            .map(|(_, flag)| flag.to_string())
+            .chain(self.extra_args.iter().cloned())
+            .collect()
+    }
+}
+
+/// Runs a git command and returns its trimmed stdout, failing on a non-zero exit.
+pub fn output(args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to execute git {}", args.join(" ")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git {} failed: {}", args.join(" "), stderr.trim());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim_end().to_string())
+}
+
+pub fn is_inside_work_tree() -> bool {
+    output(&["rev-parse", "--is-inside-work-tree"]).is_ok_and(|out| out == "true")
+}
+
+pub fn ensure_repository() -> Result<()> {
+    if !is_inside_work_tree() {
+        anyhow::bail!("Not inside a Git repository");
+    }
+    Ok(())
+}
+
+pub fn work_tree_root() -> Option<PathBuf> {
+    output(&["rev-parse", "--show-toplevel"]).ok().map(PathBuf::from)
+}
+
+/// Resolves a path inside the git directory, honoring worktrees and `core.hooksPath`.
+pub fn git_path(name: &str) -> Result<PathBuf> {
+    output(&["rev-parse", "--git-path", name]).map(PathBuf::from)
+}
+
+pub fn version() -> Result<String> {
+    output(&["--version"])
+}
+
+/// Full message of the `HEAD` commit.
+pub fn head_message() -> Result<String> {
+    output(&["log", "-1", "--format=%B", "HEAD"])
+}
+
+/// Paths with staged changes, deletions and both sides of renames included.
+pub fn staged_files() -> Result<Vec<String>> {
+    let output = output(&["diff", "--cached", "--name-only", "--no-renames"])?;
+    Ok(output.lines().map(str::to_string).collect())
+}
+
+/// Writes the index to a tree object and returns its id, to restore it
+/// later with [`restore_index`].
+pub fn write_tree() -> Result<String> {
+    output(&["write-tree"])
+}
+
+pub fn restore_index(tree: &str) -> Result<()> {
+    output(&["read-tree", tree]).map(drop)
+}
+
+/// Stages only `files`, as they are in `tree`: the index is reset to `HEAD`
+/// and the other staged changes are left in `tree`. The work tree is not touched.
+pub fn stage_only(tree: &str, files: &[String]) -> Result<()> {
+    if output(&["rev-parse", "-q", "--verify", "HEAD"]).is_ok() {
+        output(&["read-tree", "HEAD"])?;
+    } else {
+        output(&["read-tree", "--empty"])?;
+    }
+    let source = format!("--source={}", tree);
+    let mut args = vec!["restore", "--staged", source.as_str(), "--"];
+    args.extend(files.iter().map(String::as_str));
+    output(&args).map(drop)
+}
+
+/// Name of the checked out branch, `None` on a detached `HEAD`.
+pub fn current_branch() -> Option<String> {
+    output(&["symbolic-ref", "--quiet", "--short", "HEAD"]).ok()
+}
+
+/// Whether tracked files have uncommitted changes.
+pub fn has_uncommitted_changes() -> Result<bool> {
+    Ok(!output(&["status", "--porcelain", "--untracked-files=no"])?.is_empty())
+}
+
+/// Whether `commit` is reachable from any remote-tracking branch.
+pub fn is_pushed(commit: &str) -> bool {
+    output(&["branch", "--remotes", "--contains", commit]).is_ok_and(|out| !out.trim().is_empty())
+}
@@ -0,0 +1,98 @@
+use crate::{config, git};
+use anyhow::{Context, Result};
+use std::{fs, path::PathBuf};
+
+/// Number of messages kept in Convinci's own history file.
+const MAX_LOCAL_ENTRIES: usize = 200;
+
+/// Number of commits read from the repository log.
+const MAX_LOG_ENTRIES: usize = 50;
+
+/// Entries are separated by NUL so messages can span several lines.
+const SEPARATOR: char = '\0';
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Source {
+    /// Committed through Convinci, in any repository.
+    Local,
+    /// Read from `git log` of the current repository.
+    Log,
+}
+
+impl Source {
+    pub fn label(self) -> &'static str {
+        match self {
+            Source::Local => "local",
+            Source::Log => "git",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub message: String,
+    pub source: Source,
+}
+
+fn local_path() -> Option<PathBuf> {
+    config::user_data_dir().map(|dir| dir.join("convinci").join("history"))
+}
+
+fn read_local() -> Vec<String> {
+    local_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map(|content| {
+            content
+                .split(SEPARATOR)
+                .map(str::trim)
+                .filter(|m| !m.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Appends a committed message to the local history, newest last.
+pub fn record(message: &str) -> Result<()> {
+    let Some(path) = local_path() else {
+        return Ok(());
+    };
+
+    let message = message.trim().to_string();
+    let mut entries = read_local();
+    entries.retain(|m| *m != message);
+    entries.push(message);
+    let skip = entries.len().saturating_sub(MAX_LOCAL_ENTRIES);
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).context("Failed to create the history directory")?;
+    }
+    let content = entries[skip..].join(&SEPARATOR.to_string());
+    fs::write(&path, content)
+        .with_context(|| format!("Failed to write history to {}", path.display()))
+}
+
+/// Recent messages, newest first: Convinci's history, then the repository log.
+pub fn entries() -> Vec<Entry> {
+    let mut entries: Vec<Entry> = read_local()
+        .into_iter()
+        .rev()
+        .map(|message| Entry {
+            message,
+            source: Source::Local,
+        })
+        .collect();
+
+    let limit = format!("-n{}", MAX_LOG_ENTRIES);
+    let log = git::output(&["log", &limit, "--format=%B%x00"]).unwrap_or_default();
+    for message in log.split(SEPARATOR).map(str::trim).filter(|m| !m.is_empty()) {
+        if !entries.iter().any(|e| e.message == message) {
+            entries.push(Entry {
+                message: message.to_string(),
+                source: Source::Log,
+            });
+        }
+    }
+
+    entries
+}
@@ -1,32 +1,118 @@
 use anyhow::{Context, Result};
+use std::env;
 use std::fs;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 
-const HOOK_SCRIPT: &str = r#"#!/bin/sh
+/// Bumped whenever `hook_script` changes so stale hooks can be detected.
+pub const HOOK_VERSION: u32 = 4;
+
+const HOOK_VERSION_MARKER: &str = "# convinci-hook-version: ";
+
+const HOOK_BINARY_MARKER: &str = "CONVINCI_BIN=";
+
+/// Environment variable that skips validation when set to anything but `0`.
+pub const SKIP_ENV_VAR: &str = "CONVINCI_SKIP_HOOK";
+
+/// Builds the commit-msg hook, pinned to the binary that installs it.
+///
+/// GUI git clients often run hooks with a minimal `PATH`, so the absolute path
+/// is tried first and a `convinci` found on `PATH` is only a fallback.
+fn hook_script(binary: &Path) -> String {
+    let binary = shell_path(binary);
+
+    format!(
+        r##"#!/bin/sh
 # Convinci commit-msg hook
+{marker}{version}
 # Validates conventional commits format
+# Set {skip}=1 to bypass validation
+
+if [ -n "${skip}" ] && [ "${skip}" != "0" ]; then
+    exit 0
+fi
+
+{bin}'{binary}'
+if [ ! -x "$CONVINCI_BIN" ]; then
+    CONVINCI_BIN=$(command -v convinci 2>/dev/null)
+fi
+if [ -z "$CONVINCI_BIN" ]; then
+    echo "convinci: executable not found. Run 'convinci hooks install' again or set {skip}=1" >&2
+    exit 1
+fi
+
+# Comment lines and the scissors section are stripped using git's comment char,
+# or string since git 2.45; `auto` is resolved by convinci
+COMMENT_CHAR=$(git config --get core.commentString 2>/dev/null || git config --get core.commentChar 2>/dev/null || echo "#")
 
-# Pass commit message file content via stdin
-cat "$1" | convinci validate -
-"#;
+"$CONVINCI_BIN" validate --comment-char "$COMMENT_CHAR" - < "$1"
+"##,
+        marker = HOOK_VERSION_MARKER,
+        version = HOOK_VERSION,
+        skip = SKIP_ENV_VAR,
+        bin = HOOK_BINARY_MARKER,
+        binary = binary.replace('\'', r"'\''"),
+    )
+}
+
+/// The path as Git for Windows' sh expects it: forward slashes and without
+/// the `\\?\` verbatim prefix `canonicalize` adds, which `[ -x ]` rejects.
+fn shell_path(path: &Path) -> String {
+    let path = path.display().to_string();
+    let path = match path.strip_prefix(r"\\?\") {
+        Some(rest) => match rest.strip_prefix(r"UNC\") {
+            Some(share) => format!(r"\\{}", share),
+            None => rest.to_string(),
+        },
+        None => path,
+    };
+    path.replace('\\', "/")
+}
+
+/// The binary recorded by [`hook_script`], undoing its shell quoting.
+fn pinned_binary(script: &str) -> Option<PathBuf> {
+    script
+        .lines()
+        .find_map(|line| line.strip_prefix(HOOK_BINARY_MARKER))
+        .map(|path| PathBuf::from(path.trim().trim_matches('\'').replace(r"'\''", "'")))
+}
+
+/// State of the commit-msg hook in the current repository.
+#[derive(Debug)]
+pub enum HookState {
+    Missing,
+    /// A hook exists but was not installed by Convinci.
+    Foreign,
+    /// A Convinci hook, with the version and binary it was generated with (if recorded).
+    Installed {
+        version: Option<u32>,
+        binary: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug)]
+pub struct HookStatus {
+    pub path: PathBuf,
+    pub state: HookState,
+}
+
+fn hook_path() -> Result<PathBuf> {
+    crate::git::ensure_repository()?;
+    crate::git::git_path("hooks/commit-msg")
+}
 
 pub fn install_hook() -> Result<()> {
-    let git_dir = Path::new(".git");
-    if !git_dir.exists() {
-        anyhow::bail!("Not a Git repository");
-    }
+    let hook_path = hook_path()?;
 
-    let hooks_dir = git_dir.join("hooks");
-    if !hooks_dir.exists() {
-        fs::create_dir(&hooks_dir).context("Failed to create hooks directory")?;
+    if let Some(hooks_dir) = hook_path.parent()
+        && !hooks_dir.exists()
+    {
+        fs::create_dir_all(hooks_dir).context("Failed to create hooks directory")?;
     }
 
-    let hook_path = hooks_dir.join("commit-msg");
-
     // Check if hook already exists
     if hook_path.exists() {
         let existing_content = fs::read_to_string(&hook_path).context("Failed to read existing hook")?;
@@ -42,8 +128,10 @@ pub fn install_hook() -> Result<()> {
     }
 
     // Write hook script
+    let binary = env::current_exe().context("Failed to locate the convinci executable")?;
+    let binary = binary.canonicalize().unwrap_or(binary);
     let mut file = fs::File::create(&hook_path).context("Failed to create hook file")?;
-    file.write_all(HOOK_SCRIPT.as_bytes())
+    file.write_all(hook_script(&binary).as_bytes())
         .context("Failed to write hook script")?;
 
     // Set executable permissions (Unix only)
@@ -59,7 +147,7 @@ pub fn install_hook() -> Result<()> {
 }
 
 pub fn uninstall_hook() -> Result<()> {
-    let hook_path = Path::new(".git/hooks/commit-msg");
+    let hook_path = hook_path()?;
 
     if !hook_path.exists() {
         println!("No Convinci hook found");
@@ -75,4 +163,135 @@ pub fn uninstall_hook() -> Result<()> {
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+pub fn hook_status() -> Result<HookStatus> {
+    let path = hook_path()?;
+
+    if !path.exists() {
+        return Ok(HookStatus { path, state: HookState::Missing });
+    }
+
+    let content = fs::read_to_string(&path).context("Failed to read hook file")?;
+    let state = if content.contains("convinci") {
+        let version = content
+            .lines()
+            .find_map(|line| line.strip_prefix(HOOK_VERSION_MARKER))
+            .and_then(|v| v.trim().parse().ok());
+        let binary = pinned_binary(&content);
+        HookState::Installed { version, binary }
+    } else {
+        HookState::Foreign
+    };
+
+    Ok(HookStatus { path, state })
+}
+
+/// Looks up an executable on `PATH`, the same way the hook's shell would.
+pub fn find_in_path(name: &str) -> Option<PathBuf> {
+    let file_name = if cfg!(windows) {
+        format!("{}.exe", name)
+    } else {
+        name.to_string()
+    };
+
+    env::split_paths(&env::var_os("PATH")?)
+        .map(|dir| dir.join(&file_name))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Prints the hook report, returning `false` when something needs attention.
+pub fn print_status() -> Result<bool> {
+    let status = hook_status()?;
+    let mut healthy = true;
+    let mut pinned_binary = false;
+
+    match status.state {
+        HookState::Missing => {
+            healthy = false;
+            println!("❌ commit-msg hook: not installed (run `convinci hooks install`)");
+        }
+        HookState::Foreign => {
+            healthy = false;
+            println!(
+                "⚠️  commit-msg hook: not managed by Convinci ({})",
+                status.path.display()
+            );
+        }
+        HookState::Installed { version, binary } => {
+            if version == Some(HOOK_VERSION) {
+                println!(
+                    "✅ commit-msg hook: installed, version {} ({})",
+                    HOOK_VERSION,
+                    status.path.display()
+                );
+            } else {
+                healthy = false;
+                println!(
+                    "⚠️  commit-msg hook: outdated, version {} (current: {}). Run `convinci hooks install` to update",
+                    version.map_or("unknown".to_string(), |v| v.to_string()),
+                    HOOK_VERSION
+                );
+            }
+
+            match binary {
+                Some(binary) if binary.is_file() => {
+                    pinned_binary = true;
+                    println!("✅ hook binary: {}", binary.display());
+                }
+                Some(binary) => {
+                    println!(
+                        "⚠️  hook binary: {} no longer exists, falling back to PATH",
+                        binary.display()
+                    );
+                }
+                None => {}
+            }
+        }
+    }
+
+    match find_in_path("convinci") {
+        Some(path) => println!("✅ convinci on PATH: {}", path.display()),
+        None if pinned_binary => {
+            println!("⚠️  convinci on PATH: not found, the hook cannot fall back to it");
+        }
+        None => {
+            healthy = false;
+            println!("❌ convinci on PATH: not found, the hook cannot run `convinci validate`");
+        }
+    }
+
+    Ok(healthy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pins_the_binary_with_shell_quoting() {
+        let binary = Path::new("/opt/it's here/convinci");
+        let script = hook_script(binary);
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains(&format!("{}{}\n", HOOK_VERSION_MARKER, HOOK_VERSION)));
+        assert!(script.contains(r"CONVINCI_BIN='/opt/it'\''s here/convinci'"));
+        assert_eq!(pinned_binary(&script).as_deref(), Some(binary));
+    }
+
+    #[test]
+    fn writes_windows_paths_for_sh() {
+        let script = hook_script(Path::new(r"\\?\C:\Tools\convinci.exe"));
+        assert!(script.contains("CONVINCI_BIN='C:/Tools/convinci.exe'"));
+
+        assert_eq!(shell_path(Path::new(r"\\?\UNC\server\tools\convinci.exe")), "//server/tools/convinci.exe");
+        assert_eq!(shell_path(Path::new(r"C:\Tools\convinci.exe")), "C:/Tools/convinci.exe");
+    }
+
+    #[test]
+    fn reads_the_comment_char_from_git() {
+        let script = hook_script(Path::new("/usr/bin/convinci"));
+        assert!(script.contains("git config --get core.commentString"));
+        assert!(script.contains(r#"validate --comment-char "$COMMENT_CHAR" - < "$1""#));
+        assert!(script.contains(&format!(r#"[ -n "${}" ]"#, SKIP_ENV_VAR)));
+    }
+}
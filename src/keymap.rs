@@ -0,0 +1,315 @@
+use anyhow::{Context, Result};
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::fmt;
+use toml::{Table, Value};
+
+/// Actions that can be bound to keys in the `[keys]` table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    Confirm,
+    NextField,
+    PrevField,
+    ToggleBreaking,
+    OpenEditor,
+    History,
+    Presets,
+    Quit,
+}
+
+impl Action {
+    pub const ALL: [Action; 8] = [
+        Action::Confirm,
+        Action::NextField,
+        Action::PrevField,
+        Action::ToggleBreaking,
+        Action::OpenEditor,
+        Action::History,
+        Action::Presets,
+        Action::Quit,
+    ];
+
+    /// Name used in the configuration file.
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Confirm => "confirm",
+            Action::NextField => "next-field",
+            Action::PrevField => "prev-field",
+            Action::ToggleBreaking => "toggle-breaking",
+            Action::OpenEditor => "open-editor",
+            Action::History => "history",
+            Action::Presets => "presets",
+            Action::Quit => "quit",
+        }
+    }
+
+    fn defaults(self) -> &'static [&'static str] {
+        match self {
+            // Ctrl+Enter needs the kitty keyboard protocol, Ctrl+S works everywhere
+            Action::Confirm => &["ctrl+enter", "ctrl+s"],
+            Action::NextField => &["tab"],
+            Action::PrevField => &["shift+tab"],
+            Action::ToggleBreaking => &["ctrl+b"],
+            Action::OpenEditor => &["ctrl+e"],
+            Action::History => &["ctrl+r"],
+            Action::Presets => &["ctrl+p"],
+            Action::Quit => &["ctrl+c", "ctrl+q"],
+        }
+    }
+}
+
+/// A key with its modifiers, such as `ctrl+enter`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let invalid = || format!("Invalid key `{}`", text);
+        let lower = text.trim().to_ascii_lowercase();
+        let mut parts: Vec<&str> = lower.split('+').collect();
+        // `ctrl++` binds the plus key itself
+        if lower.ends_with("++") {
+            parts.truncate(parts.len() - 2);
+            parts.push("+");
+        }
+        let key = parts.pop().filter(|k| !k.is_empty()).ok_or_else(invalid)?;
+
+        let mut modifiers = KeyModifiers::NONE;
+        for part in parts {
+            modifiers |= match part {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" | "option" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                "super" | "cmd" => KeyModifiers::SUPER,
+                _ => return Err(format!("Unknown modifier `{}` in `{}`", part, text)),
+            };
+        }
+
+        let code = match key {
+            "enter" | "return" => KeyCode::Enter,
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "esc" | "escape" => KeyCode::Esc,
+            "space" => KeyCode::Char(' '),
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            _ if key.len() > 1 && key.starts_with('f') => {
+                let n: u8 = key[1..].parse().map_err(|_| invalid())?;
+                if !(1..=12).contains(&n) {
+                    return Err(invalid());
+                }
+                KeyCode::F(n)
+            }
+            _ => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return Err(invalid()),
+                }
+            }
+        };
+
+        Ok(Self { code, modifiers })
+    }
+
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        let (code, modifiers) = normalize(key);
+        self.code == code && self.modifiers == modifiers
+    }
+}
+
+/// Brings terminal-specific reports of the same key to one form: uppercase
+/// letters become lowercase with Shift, and Shift is implied by BackTab.
+fn normalize(key: &KeyEvent) -> (KeyCode, KeyModifiers) {
+    let mut modifiers = key.modifiers;
+    let code = match key.code {
+        KeyCode::Char(c) if c.is_ascii_uppercase() => {
+            modifiers |= KeyModifiers::SHIFT;
+            KeyCode::Char(c.to_ascii_lowercase())
+        }
+        KeyCode::BackTab => {
+            modifiers.remove(KeyModifiers::SHIFT);
+            KeyCode::BackTab
+        }
+        code => code,
+    };
+    (code, modifiers)
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, label) in [
+            (KeyModifiers::CONTROL, "Ctrl+"),
+            (KeyModifiers::ALT, "Alt+"),
+            (KeyModifiers::SUPER, "Super+"),
+            (KeyModifiers::SHIFT, "Shift+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(label)?;
+            }
+        }
+        match self.code {
+            KeyCode::BackTab => f.write_str("Shift+Tab"),
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(c) => write!(f, "{}", c.to_ascii_uppercase()),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            KeyCode::PageUp => f.write_str("PageUp"),
+            KeyCode::PageDown => f.write_str("PageDown"),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+/// Keys bound to each [`Action`].
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(Action, Vec<KeyBinding>)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = Action::ALL
+            .iter()
+            .map(|&action| {
+                let keys = action
+                    .defaults()
+                    .iter()
+                    .map(|key| KeyBinding::parse(key).expect("valid default key"))
+                    .collect();
+                (action, keys)
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// The action bound to `key`, if any.
+    pub fn action(&self, key: &KeyEvent) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(_, keys)| keys.iter().any(|binding| binding.matches(key)))
+            .map(|(action, _)| *action)
+    }
+
+    pub fn keys(&self, action: Action) -> &[KeyBinding] {
+        self.bindings
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, keys)| keys.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// First key bound to `action`, for hints. `None` when it is unbound.
+    pub fn label(&self, action: Action) -> Option<String> {
+        self.keys(action).first().map(KeyBinding::to_string)
+    }
+
+    fn set(&mut self, action: Action, keys: Vec<KeyBinding>) {
+        if let Some((_, bound)) = self.bindings.iter_mut().find(|(a, _)| *a == action) {
+            *bound = keys;
+        }
+    }
+}
+
+/// Parses the `[keys]` table. Each action takes a key or a list of keys; an
+/// empty list unbinds it.
+pub fn parse(table: &Table) -> Result<Keymap> {
+    let mut keymap = Keymap::default();
+
+    for (name, value) in table {
+        let action = Action::ALL
+            .into_iter()
+            .find(|a| a.name() == name)
+            .with_context(|| {
+                let names: Vec<&str> = Action::ALL.iter().map(|a| a.name()).collect();
+                format!("Unknown action `{}`. Available: {}", name, names.join(", "))
+            })?;
+
+        let keys = match value {
+            Value::String(key) => vec![key.clone()],
+            _ => crate::config::string_list(value, name)?,
+        };
+        let keys = keys
+            .iter()
+            .map(|key| KeyBinding::parse(key).map_err(anyhow::Error::msg))
+            .collect::<Result<Vec<_>>>()
+            .with_context(|| format!("Invalid binding for `{}`", name))?;
+        keymap.set(action, keys);
+    }
+
+    Ok(keymap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(spec: &str) -> KeyBinding {
+        KeyBinding::parse(spec).unwrap()
+    }
+
+    fn event(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn parses_keys_with_modifiers() {
+        assert_eq!(key("ctrl+enter"), KeyBinding { code: KeyCode::Enter, modifiers: KeyModifiers::CONTROL });
+        assert_eq!(key("Alt+S"), KeyBinding { code: KeyCode::Char('s'), modifiers: KeyModifiers::ALT });
+        assert_eq!(key("f2"), KeyBinding { code: KeyCode::F(2), modifiers: KeyModifiers::NONE });
+        assert_eq!(key("ctrl++"), KeyBinding { code: KeyCode::Char('+'), modifiers: KeyModifiers::CONTROL });
+        assert_eq!(key("shift+tab"), KeyBinding { code: KeyCode::BackTab, modifiers: KeyModifiers::NONE });
+        assert_eq!(key("ctrl+shift+space").to_string(), "Ctrl+Shift+Space");
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert_eq!(KeyBinding::parse("hyper+a").unwrap_err(), "Unknown modifier `hyper` in `hyper+a`");
+        assert_eq!(KeyBinding::parse("f13").unwrap_err(), "Invalid key `f13`");
+        assert_eq!(KeyBinding::parse("ctrl+").unwrap_err(), "Invalid key `ctrl+`");
+        assert_eq!(KeyBinding::parse("enterr").unwrap_err(), "Invalid key `enterr`");
+    }
+
+    #[test]
+    fn matches_terminal_variants() {
+        assert!(key("shift+a").matches(&event(KeyCode::Char('A'), KeyModifiers::NONE)));
+        assert!(key("shift+tab").matches(&event(KeyCode::BackTab, KeyModifiers::SHIFT)));
+        assert!(!key("ctrl+s").matches(&event(KeyCode::Char('s'), KeyModifiers::NONE)));
+    }
+
+    #[test]
+    fn overrides_and_unbinds_actions() {
+        let table: Table = toml::from_str("confirm = \"alt+enter\"\nquit = []\nhistory = [\"f2\", \"ctrl+r\"]").unwrap();
+        let keymap = parse(&table).unwrap();
+
+        assert_eq!(keymap.action(&event(KeyCode::Enter, KeyModifiers::ALT)), Some(Action::Confirm));
+        assert_eq!(keymap.action(&event(KeyCode::Char('s'), KeyModifiers::CONTROL)), None);
+        assert_eq!(keymap.action(&event(KeyCode::Char('c'), KeyModifiers::CONTROL)), None);
+        assert_eq!(keymap.label(Action::Quit), None);
+        assert_eq!(keymap.label(Action::History).as_deref(), Some("F2"));
+        assert_eq!(keymap.label(Action::Presets).as_deref(), Some("Ctrl+P"));
+    }
+
+    #[test]
+    fn rejects_unknown_actions_and_bindings() {
+        let error = |text: &str| format!("{:#}", parse(&toml::from_str(text).unwrap()).unwrap_err());
+
+        assert!(error("jump = \"ctrl+j\"").starts_with("Unknown action `jump`. Available: confirm, "));
+        assert_eq!(error("quit = \"ctrl+\""), "Invalid binding for `quit`: Invalid key `ctrl+`");
+    }
+}
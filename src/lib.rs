@@ -0,0 +1,35 @@
+//! Parsing, validation and generation of [Conventional Commits](https://www.conventionalcommits.org),
+//! as used by the `convinci` binary.
+//!
+//! ```
+//! use convinci::{commit::ConventionalCommit, config::AppConfig, lint};
+//!
+//! let commit = ConventionalCommit::parse("feat(api): add pagination").unwrap();
+//! assert_eq!(commit.scope.as_deref(), Some("api"));
+//!
+//! let violations = lint::lint(&commit.generate(), &AppConfig::default());
+//! assert!(violations.is_empty());
+//! ```
+//!
+//! The `tui` feature adds key bindings and themes to the configuration; the
+//! default `cli` feature builds the binary. Library users can depend on
+//! `convinci` with `default-features = false`.
+
+pub mod changelog;
+pub mod commit;
+pub mod config;
+pub mod emoji;
+pub mod git;
+#[cfg(feature = "tui")]
+pub mod keymap;
+pub mod lint;
+pub mod packages;
+pub mod preset;
+pub mod release;
+pub mod split;
+pub mod squash;
+pub mod suggest;
+pub mod template;
+#[cfg(feature = "tui")]
+pub mod theme;
+pub mod wording;
@@ -0,0 +1,205 @@
+use crate::{
+    commit::{ConventionalCommit, BREAKING_TOKENS},
+    config::AppConfig,
+    wording,
+};
+use std::fmt;
+
+/// A single rule violation found in a commit message.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Violation {
+    pub rule: String,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.rule, self.message)
+    }
+}
+
+impl Violation {
+    fn new(rule: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            rule: rule.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Checks only the header, such as a pull request title that becomes the
+/// header of a squash merge. Rules about the body and footers are skipped.
+pub fn lint_header(header: &str, config: &AppConfig) -> Vec<Violation> {
+    let header = header.lines().next().unwrap_or_default();
+    lint(header, config)
+        .into_iter()
+        .filter(|v| v.rule != "breaking-description-required")
+        .collect()
+}
+
+/// Checks a commit message against the format and the configured rules.
+pub fn lint(message: &str, config: &AppConfig) -> Vec<Violation> {
+    let commit = match ConventionalCommit::parse(message) {
+        Ok(commit) => commit,
+        Err(e) => return vec![Violation::new("header-format", e)],
+    };
+
+    let rules = &config.rules;
+    let mut violations = Vec::new();
+    let header = message.lines().next().unwrap_or_default();
+
+    if let Some(max) = rules.header_max_length {
+        let len = header.chars().count();
+        if len > max {
+            violations.push(Violation::new(
+                "header-max-length",
+                format!("Header is {} characters long, the maximum is {}", len, max),
+            ));
+        }
+    }
+
+    if rules.type_enum && !config.types.contains(&commit.commit_type) {
+        violations.push(Violation::new(
+            "type-enum",
+            format!(
+                "Type `{}` is not allowed. Use one of: {}",
+                commit.commit_type,
+                config.types.join(", ")
+            ),
+        ));
+    }
+
+    match &commit.scope {
+        Some(scope) if rules.scope_enum && !config.scopes.contains(scope) => {
+            violations.push(Violation::new(
+                "scope-enum",
+                format!(
+                    "Scope `{}` is not allowed. Use one of: {}",
+                    scope,
+                    config.scopes.join(", ")
+                ),
+            ));
+        }
+        None if rules.scope_required => {
+            violations.push(Violation::new("scope-required", "A scope is required"));
+        }
+        _ => {}
+    }
+
+    for issue in wording::check(&commit.description, config) {
+        violations.push(Violation::new(issue.rule, issue.message));
+    }
+
+    if commit.breaking_change
+        && rules.breaking_description_required
+        && commit.breaking_change_description.trim().is_empty()
+    {
+        violations.push(Violation::new(
+            "breaking-description-required",
+            "Breaking changes need a `BREAKING CHANGE: <description>` footer",
+        ));
+    }
+
+    // Footer tokens are case sensitive, `Breaking-Change:` is not a breaking change.
+    // Only the footer section is checked, the body is free to say "Breaking change:".
+    let misspelled_token = commit.footers.iter().find_map(|footer| {
+        let trailer = format!("{}: {}", footer.token, footer.value);
+        trailer
+            .lines()
+            .find(|line| {
+                BREAKING_TOKENS.iter().any(|token| {
+                    line.get(..token.len() + 1)
+                        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(&format!("{}:", token)))
+                        && !line.starts_with(token)
+                })
+            })
+            .map(str::to_string)
+    });
+    if let Some(line) = misspelled_token {
+        violations.push(Violation::new(
+            "breaking-token",
+            format!("Breaking change tokens must be uppercase: {}", line),
+        ));
+    }
+
+    if let (Some(max), Some(body)) = (rules.body_max_line_length, &commit.body)
+        && let Some(line) = body.lines().find(|line| line.chars().count() > max)
+    {
+        violations.push(Violation::new(
+            "body-max-line-length",
+            format!("Body line exceeds {} characters: {}", max, line),
+        ));
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(message: &str, config: &AppConfig) -> Vec<String> {
+        lint(message, config).into_iter().map(|v| v.rule).collect()
+    }
+
+    #[test]
+    fn accepts_any_conventional_commit_by_default() {
+        let config = AppConfig::default();
+        assert!(lint("wip: stuff", &config).is_empty());
+        assert!(lint(&format!("feat: {}", "x".repeat(200)), &config).is_empty());
+        assert_eq!(rules("Add stuff", &config), ["header-format"]);
+    }
+
+    #[test]
+    fn applies_the_configured_rules() {
+        let mut config = AppConfig::default();
+        config.rules.header_max_length = Some(20);
+        config.rules.body_max_line_length = Some(10);
+        config.rules.type_enum = true;
+        config.rules.scope_enum = true;
+        config.rules.breaking_description_required = true;
+
+        assert!(lint("feat(api): add x", &config).is_empty());
+        assert_eq!(
+            rules("wip(nope)!: a long description\n\nshort\nthis line is too long", &config),
+            ["header-max-length", "type-enum", "scope-enum", "breaking-description-required", "body-max-line-length"]
+        );
+
+        config.rules.scope_required = true;
+        assert_eq!(rules("feat: add x", &config), ["scope-required"]);
+    }
+
+    #[test]
+    fn checks_breaking_tokens_only_in_footers() {
+        let config = AppConfig::default();
+        assert!(lint("feat: x\n\nBreaking change: explained in prose", &config).is_empty());
+        assert!(lint("feat: x\n\nBREAKING CHANGE: y", &config).is_empty());
+        assert_eq!(rules("feat: x\n\nBody.\n\nBreaking-Change: y", &config), ["breaking-token"]);
+        assert_eq!(rules("feat: x\n\nRefs: #1\nbreaking change: y", &config), ["breaking-token"]);
+    }
+
+    #[test]
+    fn reports_wording_issues() {
+        let mut config = AppConfig::default();
+        config.rules.description_no_period = true;
+        config.rules.banned_words = vec!["wip".to_string()];
+        assert_eq!(rules("fix: WIP handle it.", &config), ["banned-words", "description-no-period"]);
+    }
+
+    #[test]
+    fn header_checks_skip_body_and_footer_rules() {
+        let mut config = AppConfig::default();
+        config.rules.breaking_description_required = true;
+        config.rules.header_max_length = Some(10);
+        assert!(lint_header("feat!: x", &config).is_empty());
+        assert_eq!(
+            lint_header("feat!: too long header\n\nignored", &config)
+                .into_iter()
+                .map(|v| v.rule)
+                .collect::<Vec<_>>(),
+            ["header-max-length"]
+        );
+        assert_eq!(lint_header("Update README", &config)[0].rule, "header-format");
+    }
+}
@@ -1,19 +1,41 @@
-mod commit;
-mod config;
+mod doctor;
+mod draft;
+mod history;
 mod hooks;
+mod prompt;
+mod reword;
 mod tui;
 
-use crate::config::AppConfig;
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
+use convinci::{changelog, commit, config, git, keymap, lint, packages, preset, release, split, squash, suggest, wording};
 use ratatui::crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind,
+        KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{
+        disable_raw_mode, enable_raw_mode, supports_keyboard_enhancement, EnterAlternateScreen,
+        LeaveAlternateScreen,
+    },
+};
+use ratatui::{prelude::*, TerminalOptions, Viewport};
+use std::{
+    fs,
+    io::{self, IsTerminal, Read, Write},
+    panic,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::Arc,
 };
-use ratatui::prelude::*;
-use std::{io, panic, process::Command};
-use std::io::Read;
+
+/// Scratch file in the git directory used by the open-editor action.
+const EDIT_FILE: &str = "CONVINCI_EDITMSG";
+
+/// Height of the viewport in inline mode when the terminal is too short for
+/// the full form, which then shows one field at a time.
+const INLINE_HEIGHT: u16 = 12;
 
 #[derive(Parser, Debug)]
 #[command(
@@ -37,28 +59,111 @@ Usage:
 
 Options:
   -d, --demo     Run in demo mode (no actual commit, interactive mode only)
+  --amend        Amend the last commit, prefilling the form from its message
+  --retry        Reopen the message of the last failed commit
+  -i, --inline   Render below the prompt instead of taking over the screen
+  -p, --preset   Start from a preset defined in the configuration
+  -s, --signoff  Add a Signed-off-by trailer
+  -S, --gpg-sign GPG-sign the commit
+  --no-verify    Bypass the pre-commit and commit-msg hooks
+  --allow-empty  Allow a commit without changes
+  -- <ARGS>...   Extra arguments forwarded to `git commit`
 
 Commands:
   hooks          Manage Git hooks for commit validation
   validate       Validate a commit message
+  parse          Print the structure of a commit message, optionally as JSON
+  pr-title       Validate a pull request title or suggest one from its commits
+  release-notes  Render the notes of one release from a template
+  bump           Show (or write) the next version of each package
+  changelog      Print (or prepend to CHANGELOG.md) the next release of each package
+  reword         Rewrite the commits of a range that don't follow the rules
+  doctor         Check hook, PATH, Git and configuration setup
+  commit         Create a commit from flags, without the interactive UI
   help           Print this message or the help of the given subcommand(s)
 
 Examples:
   convinci              # Run interactive mode (default)
   convinci --demo       # Run in demo mode (only prints the commit)
+  convinci --amend -s   # Reword the last commit and sign it off
+  convinci -p deps-bump # Start from the `deps-bump` preset
   convinci hooks install # Install commit-msg hook
   convinci validate "feat: add new feature" # Validate a commit message
+  convinci parse --json "fix(api)!: drop v1" # Print the parsed message as JSON
+  convinci pr-title --commits main..HEAD # Suggest a squash merge title and body
   convinci hooks uninstall # Uninstall commit-msg hook
+  convinci hooks status  # Check the installed commit-msg hook
+  convinci doctor        # Diagnose the whole setup
+  convinci release-notes v1.2.0 | gh release create v1.2.0 --notes-file -
+  convinci bump --write && convinci changelog --write # Prepare a release
+  convinci reword main..HEAD # Fix the messages of a feature branch
+  convinci commit -t fix --scope parser --description "handle empty input" --footer Refs=#12
 "#
 )]
 struct Cli {
     #[arg(short, long)]
     demo: bool,
 
+    /// Reopen the message of the last failed commit
+    #[arg(long)]
+    retry: bool,
+
+    /// Render below the prompt instead of taking over the screen
+    #[arg(short, long)]
+    inline: bool,
+
+    /// Start from a preset defined in the configuration
+    #[arg(short, long, value_name = "NAME")]
+    preset: Option<String>,
+
+    #[command(flatten)]
+    git: GitCommitArgs,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
 
+/// Options forwarded to `git commit`, shared by interactive mode and `convinci commit`.
+#[derive(Args, Debug)]
+struct GitCommitArgs {
+    /// Amend the last commit (interactive mode prefills the form from its message)
+    #[arg(long)]
+    amend: bool,
+
+    /// Add a Signed-off-by trailer
+    #[arg(short, long)]
+    signoff: bool,
+
+    /// GPG-sign the commit
+    #[arg(short = 'S', long)]
+    gpg_sign: bool,
+
+    /// Bypass the pre-commit and commit-msg hooks
+    #[arg(long)]
+    no_verify: bool,
+
+    /// Allow a commit without changes
+    #[arg(long)]
+    allow_empty: bool,
+
+    /// Extra arguments forwarded to `git commit`
+    #[arg(last = true, value_name = "GIT_ARGS")]
+    git_args: Vec<String>,
+}
+
+impl From<GitCommitArgs> for git::CommitOptions {
+    fn from(args: GitCommitArgs) -> Self {
+        Self {
+            amend: args.amend,
+            signoff: args.signoff,
+            gpg_sign: args.gpg_sign,
+            no_verify: args.no_verify,
+            allow_empty: args.allow_empty,
+            extra_args: args.git_args,
+        }
+    }
+}
+
 #[derive(Subcommand, Debug)]
 enum Commands {
     Hooks {
@@ -68,6 +173,124 @@ enum Commands {
 
     Validate {
         message: String,
+
+        /// Strip lines starting with this character and the scissors section,
+        /// as set in git's `core.commentChar` (`auto` strips every character
+        /// git may pick)
+        #[arg(long, value_name = "CHAR")]
+        comment_char: Option<String>,
+
+        /// Only check the first line, skipping body and footer rules
+        #[arg(long)]
+        header_only: bool,
+    },
+
+    /// Validate a pull request title, or suggest a title and body from its commits
+    PrTitle {
+        /// Title to validate as the header of the squash commit
+        title: Option<String>,
+
+        /// Commits of the pull request, as a range such as `main..HEAD` or
+        /// `-` for NUL-separated messages on stdin (`git log -z --format=%B`)
+        #[arg(long, value_name = "RANGE")]
+        commits: Option<String>,
+    },
+
+    /// Print the structure of a commit message, optionally as JSON
+    Parse {
+        /// The message, or `-` to read it from stdin
+        message: String,
+
+        /// Strip lines starting with this character and the scissors section,
+        /// as set in git's `core.commentChar` (`auto` strips every character
+        /// git may pick)
+        #[arg(long, value_name = "CHAR")]
+        comment_char: Option<String>,
+
+        /// Print the commit and its rule violations as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Render the notes of one release from a template
+    ReleaseNotes {
+        /// Tag of the release; if it doesn't exist yet, notes cover up to HEAD
+        tag: String,
+
+        /// Start after this revision instead of the previous tag
+        #[arg(long, value_name = "REV")]
+        from: Option<String>,
+
+        /// Template file (built-in Markdown template by default)
+        #[arg(long, value_name = "FILE")]
+        template: Option<PathBuf>,
+    },
+
+    /// Show the next version of each package from the commits since its latest tag
+    Bump {
+        /// Only this package
+        #[arg(short, long, value_name = "NAME")]
+        package: Option<String>,
+
+        /// Update the versions in the Cargo.toml manifests
+        #[arg(long)]
+        write: bool,
+    },
+
+    /// Print the changelog of the next release of each package
+    Changelog {
+        /// Only this package
+        #[arg(short, long, value_name = "NAME")]
+        package: Option<String>,
+
+        /// Prepend the release to the CHANGELOG.md of each package
+        #[arg(long)]
+        write: bool,
+    },
+
+    /// Rewrite the messages of the commits in a range that don't follow the rules
+    Reword {
+        /// Commits to check, such as `main..HEAD`
+        range: String,
+
+        /// Also rewrite protected branches and pushed commits
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Check hook, PATH, Git and configuration setup
+    Doctor,
+
+    /// Create a commit from flags, without the interactive UI
+    Commit {
+        /// Commit type (e.g. feat, fix)
+        #[arg(short = 't', long = "type", value_name = "TYPE")]
+        commit_type: String,
+
+        #[arg(long)]
+        scope: Option<String>,
+
+        /// Commit description, the part after `type(scope): `
+        #[arg(long)]
+        description: String,
+
+        #[arg(short, long)]
+        body: Option<String>,
+
+        /// Mark as a breaking change, with an optional description
+        #[arg(long, value_name = "DESCRIPTION", num_args = 0..=1, default_missing_value = "")]
+        breaking: Option<String>,
+
+        /// Add a footer, may be repeated (e.g. --footer Refs=#123)
+        #[arg(long = "footer", value_name = "TOKEN=VALUE")]
+        footers: Vec<String>,
+
+        /// Print the message instead of committing
+        #[arg(long)]
+        dry_run: bool,
+
+        #[command(flatten)]
+        git: GitCommitArgs,
     },
 }
 
@@ -75,6 +298,8 @@ enum Commands {
 enum HooksCommand {
     Install,
     Uninstall,
+    /// Show whether the commit-msg hook is installed and up to date
+    Status,
 }
 
 fn main() -> Result<()> {
@@ -84,72 +309,380 @@ fn main() -> Result<()> {
         Some(Commands::Hooks { command }) => match command {
             HooksCommand::Install => hooks::install_hook(),
             HooksCommand::Uninstall => hooks::uninstall_hook(),
+            HooksCommand::Status => {
+                if !hooks::print_status()? {
+                    std::process::exit(1);
+                }
+                Ok(())
+            }
         },
-        Some(Commands::Validate { message }) => {
-            let message = if message == "-" {
-                // Read from stdin
-                let mut buffer = String::new();
-                io::stdin().read_to_string(&mut buffer)?;
-                buffer
-            } else {
-                message
-            };
+        Some(Commands::Validate { message, comment_char, header_only }) => {
+            let message = read_message(message, comment_char.as_deref())?;
+
+            if commit::is_exempt(&message) {
+                println!("Skipping validation of a git-generated message");
+                return Ok(());
+            }
 
-            validate_commit_message(&message)?;
+            let config = config::load()?;
+            if header_only {
+                report_violations(lint::lint_header(&message, &config))?;
+            } else {
+                validate_commit_message(&message, &config)?;
+            }
             println!("✅ Commit message is valid!");
             Ok(())
         },
-        None => run_interactive(cli.demo),
+        Some(Commands::PrTitle { title, commits }) => run_pr_title(title.as_deref(), commits.as_deref()),
+        Some(Commands::Parse { message, comment_char, json }) => {
+            let message = read_message(message, comment_char.as_deref())?;
+            let commit = commit::ConventionalCommit::parse(&message).map_err(anyhow::Error::msg)?;
+            let config = config::load()?;
+            let violations = lint::lint(&message, &config);
+
+            if json {
+                let output = ParseOutput { commit, violations };
+                println!("{}", serde_json::to_string_pretty(&output)?);
+            } else {
+                print_parsed(&commit, &violations);
+            }
+            Ok(())
+        },
+        Some(Commands::ReleaseNotes { tag, from, template }) => {
+            git::ensure_repository()?;
+            let template = template
+                .map(|path| {
+                    fs::read_to_string(&path)
+                        .with_context(|| format!("Failed to read template {}", path.display()))
+                })
+                .transpose()?;
+            let notes = release::Release::read(&tag, from.as_deref())?.render(template.as_deref())?;
+            print!("{}", notes);
+            Ok(())
+        },
+        Some(Commands::Bump { package, write }) => run_bump(package.as_deref(), write),
+        Some(Commands::Changelog { package, write }) => run_changelog(package.as_deref(), write),
+        Some(Commands::Reword { range, force }) => reword::run(&range, force, cli.inline),
+        Some(Commands::Doctor) => doctor::run(),
+        Some(Commands::Commit {
+            commit_type,
+            scope,
+            description,
+            body,
+            breaking,
+            footers,
+            dry_run,
+            git,
+        }) => {
+            let config = config::load()?;
+            let mut commit = commit::ConventionalCommit {
+                commit_type,
+                scope,
+                description,
+                body,
+                breaking_change: breaking.is_some(),
+                breaking_change_description: breaking.unwrap_or_default(),
+                footers: footers
+                    .iter()
+                    .map(|f| commit::Footer::from_assignment(f))
+                    .collect::<Result<_, _>>()
+                    .map_err(|e| anyhow::anyhow!(e))?,
+                ..commit::ConventionalCommit::default()
+            };
+            config.style_commit(&mut commit);
+            let message = commit.generate();
+
+            validate_commit_message(&message, &config)?;
+
+            if dry_run || cli.demo {
+                println!("{}", message);
+                Ok(())
+            } else {
+                perform_git_commit(&message, &git.into())
+            }
+        }
+        None => run_interactive(
+            cli.demo,
+            cli.retry,
+            cli.inline,
+            cli.preset.as_deref(),
+            &cli.git.into(),
+        ),
     }
 }
 
-fn run_interactive(dev_mode: bool) -> Result<()> {
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+fn run_interactive(
+    dev_mode: bool,
+    retry: bool,
+    inline: bool,
+    preset: Option<&str>,
+    options: &git::CommitOptions,
+) -> Result<()> {
+    // Create app with config from arguments
+    let mut config = config::load()?;
+    config.dev_mode = dev_mode;
+    let inline = inline || config.inline;
+    let preset = preset.map(|name| config.preset(name).cloned()).transpose()?;
+    let prefilled = options.amend || retry || preset.is_some();
+    let interactive = io::stdin().is_terminal() && io::stdout().is_terminal();
+
+    let staged = if prefilled { Vec::new() } else { git::staged_files().unwrap_or_default() };
+
+    // Staged changes across several scopes can become one commit each. Not with
+    // `--allow-empty` or git arguments such as `-a` or pathspecs, which would
+    // commit more than the files of each group.
+    let plain = !options.allow_empty && options.extra_args.is_empty();
+    if interactive && !dev_mode && !prefilled && plain {
+        let groups = split::group_files(&staged, &config);
+        if groups.len() > 1 && offer_split(&groups)? {
+            return run_split(&config, &groups, inline, options);
+        }
+    }
 
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    let mut app = tui::App::new(config);
+    app.suggest_type(&staged);
 
-    // Panic hook
-    let panic_hook = panic::take_hook();
-    panic::set_hook(Box::new(move |panic| {
-        reset_terminal().expect("Failed to reset the terminal during panic");
-        panic_hook(panic);
-    }));
+    if let Some(preset) = preset {
+        app.start_preset(preset);
+    }
 
-    // Create app with config from arguments
-    let mut app = tui::App::new();
-    app.config = AppConfig {
-        dev_mode,
-    };
+    if options.amend {
+        let message = git::head_message().context("Nothing to amend")?;
+        app.prefill(commit::ConventionalCommit::parse_lenient(&message));
+    }
 
-    run_app(&mut terminal, &mut app)?;
+    if retry {
+        let message_path = git::git_path(git::MESSAGE_FILE)?;
+        let message = fs::read_to_string(&message_path)
+            .with_context(|| format!("No failed commit to retry ({})", message_path.display()))?;
+        app.prefill(commit::ConventionalCommit::parse_lenient(&message));
+    }
+
+    app.history = history::entries();
+
+    // Only offer the autosaved draft when the form was not prefilled
+    let draft_path = draft::path();
+    if !prefilled {
+        app.draft_offer = draft_path.as_deref().and_then(draft::load);
+    }
+
+    if interactive {
+        run_tui(&mut app, inline, draft_path.as_deref())?;
+    } else {
+        let initial = std::mem::take(&mut app.commit);
+        if let Some(commit) = prompt::compose(&app.config, initial)? {
+            app.commit = commit;
+            app.should_confirm = true;
+        }
+    }
 
-    // Final cleanup
-    reset_terminal()?;
     if app.should_confirm {
+        if let Some(path) = &draft_path {
+            draft::discard(path);
+        }
+
+        app.config.style_commit(&mut app.commit);
         let commit_message = app.commit.generate();
 
         if dev_mode {
             println!("Generated commit message:\n\n{}", commit_message);
         } else {
-            perform_git_commit(&commit_message)?;
+            if inline {
+                // Keep the final message visible in the scrollback
+                println!("{}\n", commit_message);
+            }
+            perform_git_commit(&commit_message, options)?;
+        }
+    }
+    Ok(())
+}
+
+fn offer_split(groups: &[split::FileGroup]) -> Result<bool> {
+    println!("Staged changes span {} areas:", groups.len());
+    for group in groups {
+        println!("  {:<12} {}", group.scope.as_deref().unwrap_or("(no scope)"), group.files.join(", "));
+    }
+    print!("Split them into one commit per area? [y/N] ");
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+/// Composes one commit per group, staging only the files of that group each
+/// time. The original index comes back when a commit is cancelled or fails.
+fn run_split(
+    config: &config::AppConfig,
+    groups: &[split::FileGroup],
+    inline: bool,
+    options: &git::CommitOptions,
+) -> Result<()> {
+    let saved = git::write_tree()?;
+
+    for (i, group) in groups.iter().enumerate() {
+        let label = format!(
+            "{}/{} {}",
+            i + 1,
+            groups.len(),
+            group.scope.as_deref().unwrap_or("no scope")
+        );
+        match commit_group(config, group, &saved, label, inline, options) {
+            Ok(true) => {}
+            Ok(false) => {
+                git::restore_index(&saved)?;
+                println!("Split cancelled, the remaining changes are staged again");
+                return Ok(());
+            }
+            Err(e) => {
+                git::restore_index(&saved)?;
+                return Err(e);
+            }
         }
     }
     Ok(())
 }
 
-fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut tui::App) -> Result<()> {
+/// Returns `false` when the user quit instead of confirming.
+fn commit_group(
+    config: &config::AppConfig,
+    group: &split::FileGroup,
+    saved: &str,
+    label: String,
+    inline: bool,
+    options: &git::CommitOptions,
+) -> Result<bool> {
+    git::stage_only(saved, &group.files)?;
+
+    let mut app = tui::App::new(config.clone());
+    if group.scope.is_some() {
+        app.prefill(commit::ConventionalCommit {
+            commit_type: app.commit.commit_type.clone(),
+            scope: group.scope.clone(),
+            ..commit::ConventionalCommit::default()
+        });
+    }
+    app.suggest_type(&group.files);
+    app.progress = Some(label);
+    app.history = history::entries();
+    run_tui(&mut app, inline, None)?;
+
+    if !app.should_confirm {
+        return Ok(false);
+    }
+    app.config.style_commit(&mut app.commit);
+    let commit_message = app.commit.generate();
+    if inline {
+        println!("{}\n", commit_message);
+    }
+    perform_git_commit(&commit_message, options)?;
+    Ok(true)
+}
+
+fn run_tui(app: &mut tui::App, inline: bool, draft_path: Option<&Path>) -> Result<()> {
+    let enhanced = setup_terminal(inline)?;
+    let stdout = io::stdout();
+
+    let mut terminal = if inline {
+        // The full form when it fits with a line of the prompt left above it
+        let rows = ratatui::crossterm::terminal::size().map(|(_, rows)| rows).unwrap_or_default();
+        let height = if rows > tui::FULL_FORM_HEIGHT {
+            tui::FULL_FORM_HEIGHT
+        } else {
+            INLINE_HEIGHT
+        };
+        let options = TerminalOptions {
+            viewport: Viewport::Inline(height),
+        };
+        Terminal::with_options(CrosstermBackend::new(stdout), options)?
+    } else {
+        Terminal::new(CrosstermBackend::new(stdout))?
+    };
+
+    // Panic hook, restored afterwards so repeated sessions (split, reword) don't stack them
+    let panic_hook = Arc::new(panic::take_hook());
+    let previous = Arc::clone(&panic_hook);
+    panic::set_hook(Box::new(move |panic| {
+        reset_terminal(inline, enhanced).expect("Failed to reset the terminal during panic");
+        previous(panic);
+    }));
+
+    let result = run_app(&mut terminal, app, draft_path, inline, enhanced);
+
+    let _ = panic::take_hook();
+    if let Ok(panic_hook) = Arc::try_unwrap(panic_hook) {
+        panic::set_hook(panic_hook);
+    }
+
+    // Final cleanup
+    if inline {
+        terminal.clear()?;
+    }
+    reset_terminal(inline, enhanced)?;
+    result
+}
+
+/// Enters raw mode and, outside inline mode, the alternate screen. Returns
+/// whether keyboard enhancement flags were pushed, so that keys like
+/// Ctrl+Enter can be told apart from Enter.
+fn setup_terminal(inline: bool) -> Result<bool> {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+
+    if !inline {
+        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    }
+
+    let enhanced = matches!(supports_keyboard_enhancement(), Ok(true));
+    if enhanced {
+        execute!(
+            stdout,
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+        )?;
+    }
+    Ok(enhanced)
+}
+
+fn run_app<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut tui::App,
+    draft_path: Option<&Path>,
+    inline: bool,
+    enhanced: bool,
+) -> Result<()> {
+    let mut saved_draft = draft::serialize(&app.commit);
+
     while !app.should_quit {
         terminal.draw(|f| app.render(f))?;
 
-        if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Press {
-                app.handle_key(key);
+        match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => app.handle_key(key),
+            Event::Mouse(mouse) => app.handle_mouse(mouse),
+            _ => continue,
+        }
+
+        if app.open_editor {
+            app.open_editor = false;
+            reset_terminal(inline, enhanced)?;
+            let edited = edit_message(&app.commit.generate());
+            setup_terminal(inline)?;
+            terminal.clear()?;
+
+            if let Some(message) = edited? {
+                app.prefill(commit::ConventionalCommit::parse_lenient(&message));
+            }
+        }
 
-                if key.code == KeyCode::Char('q') && key.modifiers.contains(event::KeyModifiers::CONTROL) {
-                    app.should_quit = true;
+        // Autosave on every edit so nothing is lost if the terminal dies
+        if let Some(path) = draft_path {
+            if app.discard_draft {
+                app.discard_draft = false;
+                draft::discard(path);
+            } else if app.draft_offer.is_none() {
+                let current = draft::serialize(&app.commit);
+                if current != saved_draft {
+                    draft::save(path, &app.commit)?;
+                    saved_draft = current;
                 }
             }
         }
@@ -157,46 +690,287 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut tui::App) -> Result
     Ok(())
 }
 
-fn validate_commit_message(message: &str) -> Result<()> {
-    commit::ConventionalCommit::validate(message)
-        .map_err(|e| anyhow::anyhow!(e))
+/// Opens the message in the user's editor, like `git commit` does. Returns
+/// `None` when the edited message is empty.
+fn edit_message(message: &str) -> Result<Option<String>> {
+    let path = match git::git_path(EDIT_FILE) {
+        Ok(path) if git::is_inside_work_tree() => path,
+        _ => std::env::temp_dir().join(EDIT_FILE),
+    };
+    fs::write(
+        &path,
+        format!("{}\n\n# Lines starting with '#' are ignored.\n", message),
+    )
+    .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    let editor = git::output(&["var", "GIT_EDITOR"])
+        .ok()
+        .filter(|e| !e.is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+
+    // The editor setting may contain arguments, so let the shell split it
+    let status = if cfg!(windows) {
+        Command::new("cmd")
+            .args(["/C", &format!("{} \"{}\"", editor, path.display())])
+            .status()
+    } else {
+        Command::new("sh")
+            .args(["-c", &format!("{} \"$@\"", editor), &editor])
+            .arg(&path)
+            .status()
+    }
+    .with_context(|| format!("Failed to run editor `{}`", editor))?;
+
+    let edited = fs::read_to_string(&path);
+    let _ = fs::remove_file(&path);
+    if !status.success() {
+        anyhow::bail!("Editor `{}` exited with {}", editor, status);
+    }
+
+    let message = commit::strip_comments(&edited?, '#');
+    Ok(Some(message).filter(|m| !m.is_empty()))
 }
 
-fn perform_git_commit(message: &str) -> Result<()> {
-    // Check if we are in a Git repository
-    let repo_check = Command::new("git")
-        .arg("rev-parse")
-        .arg("--is-inside-work-tree")
-        .output()
-        .context("Failed to check Git repository")?;
+/// Plans the next release of every package, then keeps `package` only.
+fn plan_releases(package: Option<&str>) -> Result<(Vec<packages::PackageRelease>, PathBuf)> {
+    git::ensure_repository()?;
+    let config = config::load()?;
+    let root = git::work_tree_root().context("Failed to find the work tree root")?;
+    let releases = packages::plan(&config.release_packages(), &root)?;
+
+    if let Some(name) = package
+        && !releases.iter().any(|r| r.package.name == name)
+    {
+        let names: Vec<&str> = releases.iter().map(|r| r.package.name.as_str()).collect();
+        anyhow::bail!("Unknown package `{}`. Available: {}", name, names.join(", "));
+    }
+    Ok((releases, root))
+}
+
+fn selected(release: &packages::PackageRelease, package: Option<&str>) -> bool {
+    package.is_none_or(|name| release.package.name == name)
+}
+
+fn run_bump(package: Option<&str>, write: bool) -> Result<()> {
+    let (releases, root) = plan_releases(package)?;
+
+    let mut tags = Vec::new();
+    for release in releases.iter().filter(|r| selected(r, package)) {
+        let name = if release.package.name.is_empty() { "version" } else { release.package.name.as_str() };
+        if release.bump == changelog::Bump::None {
+            println!("{:<20} {} (no release)", name, release.current);
+            continue;
+        }
+
+        let level = format!("{:?}", release.bump).to_lowercase();
+        let reason = if release.updated_dependencies.is_empty() {
+            level
+        } else {
+            format!("{}, dependencies: {}", level, release.updated_dependencies.join(", "))
+        };
+        println!("{:<20} {} → {} ({})", name, release.current, release.next, reason);
+        tags.push(release.tag());
 
-    if !repo_check.status.success() {
-        anyhow::bail!("Not inside a Git repository");
+        if write && !packages::write_manifest(release, &releases, &root)? {
+            println!("{:<20} no Cargo.toml, version not written", "");
+        }
+    }
+
+    if !tags.is_empty() {
+        println!();
+        println!("Tags: {}", tags.join(" "));
+    }
+    Ok(())
+}
+
+fn run_changelog(package: Option<&str>, write: bool) -> Result<()> {
+    let (releases, root) = plan_releases(package)?;
+    let date = git::output(&["log", "-1", "--format=%cs", "HEAD"]).unwrap_or_default();
+
+    for release in releases
+        .iter()
+        .filter(|r| selected(r, package) && r.bump != changelog::Bump::None)
+    {
+        let section = release.changelog(&date, &releases);
+        if write {
+            let path = release.package.changelog_path(&root);
+            packages::prepend_changelog(&path, &section)?;
+            println!("✅ Updated {}", path.display());
+        } else {
+            if !release.package.name.is_empty() {
+                println!("# {}\n", release.package.name);
+            }
+            println!("{}", section);
+        }
     }
+    Ok(())
+}
+
+/// Validates `title` and, given the commits of the pull request, prints the
+/// squash commit message: the title (or a suggested one) and an aggregated body.
+fn run_pr_title(title: Option<&str>, commits: Option<&str>) -> Result<()> {
+    let config = config::load()?;
+    let title_result = title.map(|title| report_violations(lint::lint_header(title, &config)));
+
+    let Some(commits) = commits else {
+        title_result.context("Give a title to validate or --commits to suggest one")??;
+        println!("✅ PR title is valid!");
+        return Ok(());
+    };
+
+    let entries = if commits == "-" {
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer)?;
+        buffer
+            .split('\0')
+            .filter_map(|message| commit::ConventionalCommit::parse(message.trim()).ok())
+            .map(|commit| changelog::LogEntry {
+                hash: String::new(),
+                author: String::new(),
+                commit,
+                files: Vec::new(),
+            })
+            .collect()
+    } else {
+        git::ensure_repository()?;
+        changelog::read_log(commits)?
+    };
+    let mut suggestion = squash::suggest(&entries)
+        .with_context(|| format!("No conventional commits in {}", commits))?;
+    config.style_commit(&mut suggestion);
+
+    let mut message = suggestion.generate();
+    if let (Some(title), Some(Ok(()))) = (title, &title_result) {
+        // Keep the given title, the body still comes from the commits
+        let body = message.split_once('\n').map(|(_, rest)| rest).unwrap_or_default();
+        message = format!("{}\n{}", title.trim(), body).trim_end().to_string();
+    }
+    println!("{}", message);
+
+    match title_result {
+        Some(Err(e)) => Err(e.context("The PR title does not follow the rules, a suggestion is printed above")),
+        _ => Ok(()),
+    }
+}
+
+/// The message argument of `validate` and `parse`, read from stdin for `-`,
+/// with comments stripped when a comment character is given.
+fn read_message(message: String, comment_char: Option<&str>) -> Result<String> {
+    let message = if message == "-" {
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer)?;
+        buffer
+    } else {
+        message
+    };
+
+    Ok(match comment_char {
+        None => message,
+        Some("auto") => commit::strip_auto_comments(&message),
+        Some("") => commit::strip_comments(&message, '#'),
+        Some(prefix) => commit::strip_comment_prefix(&message, prefix),
+    })
+}
+
+/// Output of `convinci parse --json`.
+#[derive(serde::Serialize)]
+struct ParseOutput {
+    commit: commit::ConventionalCommit,
+    violations: Vec<lint::Violation>,
+}
+
+fn print_parsed(commit: &commit::ConventionalCommit, violations: &[lint::Violation]) {
+    println!("type:        {}", commit.commit_type);
+    if let Some(scope) = &commit.scope {
+        println!("scope:       {}", scope);
+    }
+    println!("description: {}", commit.description);
+    if let Some(emoji) = &commit.emoji {
+        println!("emoji:       {}", emoji.text);
+    }
+    if commit.breaking_change {
+        match commit.breaking_change_description.trim() {
+            "" => println!("breaking:    yes"),
+            description => println!("breaking:    {}", description),
+        }
+    }
+    if let Some(body) = &commit.body {
+        println!("body:\n{}", body);
+    }
+    for footer in &commit.footers {
+        println!("footer:      {}: {}", footer.token, footer.value);
+    }
+
+    if violations.is_empty() {
+        println!("✅ Commit message is valid!");
+    } else {
+        println!("Violations:");
+        for violation in violations {
+            println!("  - {}", violation);
+        }
+    }
+}
+
+fn validate_commit_message(message: &str, config: &config::AppConfig) -> Result<()> {
+    report_violations(lint::lint(message, config))
+}
+
+fn report_violations(violations: Vec<lint::Violation>) -> Result<()> {
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    let details = violations
+        .iter()
+        .map(|v| format!("  - {}", v))
+        .collect::<Vec<_>>()
+        .join("\n");
+    anyhow::bail!("Commit message does not follow the rules:\n{}", details)
+}
+
+fn perform_git_commit(message: &str, options: &git::CommitOptions) -> Result<()> {
+    // Check if we are in a Git repository
+    git::ensure_repository()?;
+
+    // Commit from a file so the message keeps its formatting and survives a failed commit
+    let message_path = git::git_path(git::MESSAGE_FILE)?;
+    fs::write(&message_path, message).context("Failed to write the commit message file")?;
 
     // Execute the commit
     let status = Command::new("git")
         .arg("commit")
-        .arg("-m")
-        .arg(message)
+        .arg("-F")
+        .arg(&message_path)
+        .args(options.args())
         .status()
         .context("Failed to execute git commit")?;
 
     if status.success() {
+        let _ = fs::remove_file(&message_path);
+        // History is a convenience, a failure to record it must not fail the commit
+        let _ = history::record(message);
         println!("✅ Commit successful!");
     } else {
-        anyhow::bail!("Error executing git commit");
+        anyhow::bail!(
+            "Error executing git commit\nYour message was kept in {}. Run `convinci --retry` to edit it and try again",
+            message_path.display()
+        );
     }
 
     Ok(())
 }
 
-fn reset_terminal() -> Result<()> {
+fn reset_terminal(inline: bool, enhanced: bool) -> Result<()> {
+    if enhanced {
+        execute!(io::stdout(), PopKeyboardEnhancementFlags)?;
+    }
     disable_raw_mode()?;
-    execute!(
-        io::stdout(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
+    if !inline {
+        execute!(
+            io::stdout(),
+            LeaveAlternateScreen,
+            DisableMouseCapture
+        )?;
+    }
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,637 @@
+use crate::{
+    changelog::{self, Bump, LogEntry, Version},
+    git,
+};
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use toml::{Table, Value};
+
+/// Name of the changelog file written in each package directory.
+pub const CHANGELOG_FILE: &str = "CHANGELOG.md";
+
+/// A package of a monorepo from the `[packages]` table.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Package {
+    pub name: String,
+    /// Directory relative to the work tree root, `.` for the whole repository.
+    pub path: PathBuf,
+    /// Commit scopes that belong to the package, besides the touched paths.
+    pub scopes: Vec<String>,
+    /// Names of packages this one depends on. `None` reads them from the
+    /// path dependencies of the package's `Cargo.toml`.
+    pub dependencies: Option<Vec<String>>,
+}
+
+impl Package {
+    /// The whole repository as one package, used when none are configured.
+    pub fn root() -> Self {
+        Self {
+            name: String::new(),
+            path: PathBuf::from("."),
+            scopes: Vec::new(),
+            dependencies: Some(Vec::new()),
+        }
+    }
+
+    fn is_root(&self) -> bool {
+        self.path == Path::new(".")
+    }
+
+    /// `v1.2.0` for the root package, `name@v1.2.0` for the others.
+    pub fn tag(&self, version: Version) -> String {
+        format!("{}v{}", self.tag_prefix(), version)
+    }
+
+    fn tag_prefix(&self) -> String {
+        if self.name.is_empty() {
+            String::new()
+        } else {
+            format!("{}@", self.name)
+        }
+    }
+
+    /// A commit belongs to the package when its scope is one of the
+    /// package's scopes or when it touches files in the package directory.
+    pub fn owns(&self, entry: &LogEntry) -> bool {
+        if self.is_root() {
+            return true;
+        }
+        let in_scope = entry
+            .commit
+            .scope
+            .as_deref()
+            .is_some_and(|scope| scope.split(',').any(|s| self.scopes.iter().any(|own| own == s.trim())));
+        in_scope || entry.files.iter().any(|file| Path::new(file).starts_with(&self.path))
+    }
+
+    pub fn manifest_path(&self, root: &Path) -> PathBuf {
+        root.join(&self.path).join("Cargo.toml")
+    }
+
+    pub fn changelog_path(&self, root: &Path) -> PathBuf {
+        root.join(&self.path).join(CHANGELOG_FILE)
+    }
+}
+
+/// Parses the `[packages]` table, one entry per package name.
+pub fn parse(table: &Table) -> Result<Vec<Package>> {
+    let packages = table
+        .iter()
+        .map(|(name, value)| {
+            let package = value
+                .as_table()
+                .with_context(|| format!("Package `{}` must be a table", name))?;
+            parse_package(name, package).with_context(|| format!("Invalid package `{}`", name))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    for package in &packages {
+        for dependency in package.dependencies.iter().flatten() {
+            if !packages.iter().any(|p| p.name == *dependency) {
+                anyhow::bail!("Package `{}` depends on unknown package `{}`", package.name, dependency);
+            }
+        }
+    }
+    Ok(packages)
+}
+
+fn parse_package(name: &str, table: &Table) -> Result<Package> {
+    let mut package = Package {
+        name: name.to_string(),
+        path: PathBuf::new(),
+        scopes: vec![name.to_string()],
+        dependencies: None,
+    };
+
+    for (key, value) in table {
+        match key.as_str() {
+            "path" => {
+                let path = value.as_str().context("`path` must be a string")?;
+                package.path = normalize(path);
+            }
+            "scopes" => package.scopes = crate::config::string_list(value, "scopes")?,
+            "dependencies" => package.dependencies = Some(crate::config::string_list(value, "dependencies")?),
+            _ => anyhow::bail!("Unknown key `{}`", key),
+        }
+    }
+
+    if package.path.as_os_str().is_empty() {
+        anyhow::bail!("`path` is required");
+    }
+    Ok(package)
+}
+
+/// Drops a leading `./` and trailing slashes, as the file paths of commits
+/// have neither. `./` alone is the whole repository.
+pub(crate) fn normalize(path: &str) -> PathBuf {
+    if path.is_empty() {
+        return PathBuf::new();
+    }
+    let mut path = path.trim_end_matches('/');
+    while let Some(rest) = path.strip_prefix("./") {
+        path = rest.trim_start_matches('/');
+    }
+    PathBuf::from(if path.is_empty() { "." } else { path })
+}
+
+/// Internal dependencies of `package`: the configured list, or the path
+/// dependencies of its `Cargo.toml` that point to another package.
+fn dependencies(package: &Package, packages: &[Package], root: &Path) -> Vec<String> {
+    if let Some(dependencies) = &package.dependencies {
+        return dependencies.clone();
+    }
+
+    let Some(manifest) = fs::read_to_string(package.manifest_path(root))
+        .ok()
+        .and_then(|content| content.parse::<Table>().ok())
+    else {
+        return Vec::new();
+    };
+    let directory = root.join(&package.path);
+
+    let mut names = Vec::new();
+    for section in ["dependencies", "build-dependencies"] {
+        let Some(entries) = manifest.get(section).and_then(Value::as_table) else {
+            continue;
+        };
+        for entry in entries.values() {
+            let Some(path) = entry.get("path").and_then(Value::as_str) else {
+                continue;
+            };
+            let target = fs::canonicalize(directory.join(path)).ok();
+            let dependency = packages.iter().find(|other| {
+                other.name != package.name && fs::canonicalize(root.join(&other.path)).ok() == target
+            });
+            if let Some(dependency) = dependency
+                && !names.contains(&dependency.name)
+            {
+                names.push(dependency.name.clone());
+            }
+        }
+    }
+    names
+}
+
+/// What the next release of one package contains.
+#[derive(Debug)]
+pub struct PackageRelease {
+    pub package: Package,
+    /// Latest release tag, `None` before the first release.
+    pub previous_tag: Option<String>,
+    pub current: Version,
+    pub next: Version,
+    pub bump: Bump,
+    pub entries: Vec<LogEntry>,
+    /// Internal dependencies released at the same time.
+    pub updated_dependencies: Vec<String>,
+}
+
+impl PackageRelease {
+    pub fn tag(&self) -> String {
+        self.package.tag(self.next)
+    }
+
+    /// The changelog section of the release.
+    pub fn changelog(&self, date: &str, releases: &[PackageRelease]) -> String {
+        let mut out = changelog::render_markdown(&format!("{} ({})", self.next, date), &self.entries);
+        if !self.updated_dependencies.is_empty() {
+            out.push_str("\n### Dependencies\n\n");
+            for name in &self.updated_dependencies {
+                if let Some(dependency) = releases.iter().find(|r| r.package.name == *name) {
+                    out.push_str(&format!("* **{}:** updated to {}\n", name, dependency.next));
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Computes the next version of every package from the commits since its
+/// latest tag. Packages whose internal dependencies are released get at
+/// least a patch release, transitively.
+pub fn plan(packages: &[Package], root: &Path) -> Result<Vec<PackageRelease>> {
+    let mut releases = packages
+        .iter()
+        .map(|package| {
+            let previous_tag = latest_tag(package);
+            let current = previous_tag
+                .as_deref()
+                .and_then(|tag| tag.strip_prefix(&package.tag_prefix()))
+                .and_then(Version::parse)
+                .or_else(|| manifest_version(&package.manifest_path(root)))
+                .unwrap_or(Version { major: 0, minor: 0, patch: 0 });
+
+            let range = match &previous_tag {
+                Some(tag) => format!("{}..HEAD", tag),
+                None => "HEAD".to_string(),
+            };
+            let entries: Vec<LogEntry> = changelog::read_log(&range)
+                .with_context(|| format!("Failed to read the commits in {}", range))?
+                .into_iter()
+                .filter(|entry| package.owns(entry))
+                .collect();
+            let bump = changelog::bump_level(&entries);
+
+            Ok(PackageRelease {
+                package: package.clone(),
+                previous_tag,
+                current,
+                next: current.bump(bump),
+                bump,
+                entries,
+                updated_dependencies: Vec::new(),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let dependencies: Vec<Vec<String>> = packages
+        .iter()
+        .map(|package| dependencies(package, packages, root))
+        .collect();
+
+    cascade(&mut releases, &dependencies);
+    Ok(releases)
+}
+
+/// Gives a patch release to packages whose dependencies (by index, from
+/// `dependencies`) are released, until nothing changes. Each pass can only
+/// raise bumps.
+fn cascade(releases: &mut [PackageRelease], dependencies: &[Vec<String>]) {
+    loop {
+        let mut changed = false;
+        for i in 0..releases.len() {
+            let updated: Vec<String> = dependencies[i]
+                .iter()
+                .filter(|name| {
+                    releases
+                        .iter()
+                        .any(|r| r.package.name == **name && r.bump != Bump::None)
+                })
+                .cloned()
+                .collect();
+            let release = &mut releases[i];
+            if updated != release.updated_dependencies {
+                release.updated_dependencies = updated;
+                changed = true;
+            }
+            if release.bump == Bump::None && !release.updated_dependencies.is_empty() {
+                release.bump = Bump::Patch;
+                release.next = release.current.bump(Bump::Patch);
+                changed = true;
+            }
+        }
+        if !changed {
+            return;
+        }
+    }
+}
+
+fn latest_tag(package: &Package) -> Option<String> {
+    let pattern = format!("{}v[0-9]*", package.tag_prefix());
+    git::output(&["describe", "--tags", "--abbrev=0", "--match", &pattern, "HEAD"]).ok()
+}
+
+/// The `version` of the `[package]` table of a `Cargo.toml`.
+fn manifest_version(path: &Path) -> Option<Version> {
+    let manifest: Table = fs::read_to_string(path).ok()?.parse().ok()?;
+    let version = manifest.get("package")?.get("version")?.as_str()?;
+    Version::parse(version)
+}
+
+/// Sets the package version in its `Cargo.toml` and the version requirement
+/// of each updated internal dependency, keeping the rest of the file as is.
+/// Returns `false` when the package has no manifest, and fails when a
+/// requirement can't be found in the file to rewrite it.
+pub fn write_manifest(release: &PackageRelease, releases: &[PackageRelease], root: &Path) -> Result<bool> {
+    let path = release.package.manifest_path(root);
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Ok(false);
+    };
+    let manifest: Table = content.parse().with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    let directory = root.join(&release.package.path);
+    let requirements: Vec<(String, Version)> = releases
+        .iter()
+        .filter(|r| release.updated_dependencies.contains(&r.package.name))
+        .flat_map(|dependency| {
+            requirement_keys(&manifest, &directory, dependency, root)
+                .into_iter()
+                .map(|key| (key, dependency.next))
+        })
+        .collect();
+
+    let mut section = String::new();
+    let mut rewritten: Vec<&str> = Vec::new();
+    let mut lines = Vec::new();
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            section = trimmed.trim_matches(|c| c == '[' || c == ']').to_string();
+        }
+        // `[dependencies.core]` holds the requirement on its own line
+        let table_key = section
+            .rsplit_once('.')
+            .filter(|(prefix, _)| prefix.ends_with("dependencies"))
+            .map(|(_, key)| key.trim_matches(|c| c == '"' || c == '\''));
+
+        let mut line = line.to_string();
+        if section == "package" && release.bump != Bump::None {
+            if let Some(updated) = replace_version(&line, r#"^(\s*version\s*=\s*")[^"]*(")"#, release.next) {
+                line = updated;
+            }
+        } else if let Some(table_key) = table_key {
+            if let Some((key, version)) = requirements.iter().find(|(key, _)| key == table_key)
+                && let Some(updated) = replace_version(&line, r#"^(\s*version\s*=\s*"[\^~=]?)[^"]*(")"#, *version)
+            {
+                line = updated;
+                rewritten.push(key);
+            }
+        } else if section.ends_with("dependencies") {
+            for (key, version) in &requirements {
+                let pattern = format!(
+                    r#"^(\s*{}\s*=\s*\{{[^}}]*\bversion\s*=\s*"[\^~=]?)[^"]*(")"#,
+                    regex::escape(key)
+                );
+                if let Some(updated) = replace_version(&line, &pattern, *version) {
+                    line = updated;
+                    rewritten.push(key);
+                }
+            }
+        }
+        lines.push(line);
+    }
+
+    if let Some((key, _)) = requirements.iter().find(|(key, _)| !rewritten.contains(&key.as_str())) {
+        anyhow::bail!(
+            "Couldn't update the version requirement of `{}` in {}. Use `{} = {{ version = \"...\" }}` or a `version` line in its own table",
+            key,
+            path.display(),
+            key
+        );
+    }
+
+    let mut updated = lines.join("\n");
+    if content.ends_with('\n') {
+        updated.push('\n');
+    }
+    fs::write(&path, updated).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(true)
+}
+
+/// Keys under which `manifest` requires a version of `dependency`: entries
+/// with a path to its directory, or naming its crate as the key or with
+/// `package`. The crate name is read from its own `Cargo.toml`.
+fn requirement_keys(manifest: &Table, directory: &Path, dependency: &PackageRelease, root: &Path) -> Vec<String> {
+    let crate_name = fs::read_to_string(dependency.package.manifest_path(root))
+        .ok()
+        .and_then(|content| content.parse::<Table>().ok())
+        .and_then(|manifest| Some(manifest.get("package")?.get("name")?.as_str()?.to_string()))
+        .unwrap_or_else(|| dependency.package.name.clone());
+    let target = fs::canonicalize(root.join(&dependency.package.path)).ok();
+
+    let platforms = manifest
+        .get("target")
+        .and_then(Value::as_table)
+        .into_iter()
+        .flat_map(|targets| targets.values().filter_map(Value::as_table));
+    let mut keys = Vec::new();
+    for table in std::iter::once(manifest).chain(platforms) {
+        for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+            let Some(entries) = table.get(section).and_then(Value::as_table) else {
+                continue;
+            };
+            for (key, entry) in entries {
+                if entry.get("version").is_none() {
+                    continue;
+                }
+                let by_path = entry
+                    .get("path")
+                    .and_then(Value::as_str)
+                    .is_some_and(|path| target.is_some() && fs::canonicalize(directory.join(path)).ok() == target);
+                let by_name = entry.get("package").and_then(Value::as_str).unwrap_or(key) == crate_name;
+                if (by_path || by_name) && !keys.contains(key) {
+                    keys.push(key.clone());
+                }
+            }
+        }
+    }
+    keys
+}
+
+fn replace_version(line: &str, pattern: &str, version: Version) -> Option<String> {
+    let regex = Regex::new(pattern).expect("valid version pattern");
+    regex.is_match(line).then(|| {
+        regex
+            .replace(line, |captures: &regex::Captures| format!("{}{}{}", &captures[1], version, &captures[2]))
+            .into_owned()
+    })
+}
+
+/// Adds `section` above the previous releases of a changelog file, creating
+/// it with a `# Changelog` title if needed.
+pub fn prepend_changelog(path: &Path, section: &str) -> Result<()> {
+    let existing = fs::read_to_string(path).unwrap_or_else(|_| "# Changelog\n".to_string());
+    let updated = if existing.starts_with("## ") {
+        format!("{}\n{}", section, existing)
+    } else {
+        match existing.find("\n## ") {
+            Some(index) => format!("{}\n\n{}\n{}", existing[..index].trim_end(), section, &existing[index + 1..]),
+            None => format!("{}\n\n{}", existing.trim_end(), section),
+        }
+    };
+    fs::write(path, updated).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commit::ConventionalCommit;
+
+    fn packages(source: &str) -> Result<Vec<Package>> {
+        parse(&source.parse::<Table>().unwrap())
+    }
+
+    fn release(name: &str, bump: Bump) -> PackageRelease {
+        let current = Version { major: 1, minor: 0, patch: 0 };
+        PackageRelease {
+            package: Package {
+                name: name.to_string(),
+                path: PathBuf::from(name),
+                scopes: vec![name.to_string()],
+                dependencies: None,
+            },
+            previous_tag: None,
+            current,
+            next: current.bump(bump),
+            bump,
+            entries: Vec::new(),
+            updated_dependencies: Vec::new(),
+        }
+    }
+
+    fn entry(message: &str, files: &[&str]) -> LogEntry {
+        LogEntry {
+            hash: String::new(),
+            author: String::new(),
+            commit: ConventionalCommit::parse(message).unwrap(),
+            files: files.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn normalizes_configured_paths() {
+        let packages = packages(
+            r#"
+            core = { path = "./crates/core/" }
+            root = { path = "./" }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(packages[0].path, Path::new("crates/core"));
+        assert_eq!(packages[1].path, Path::new("."));
+    }
+
+    #[test]
+    fn rejects_unknown_dependencies_and_missing_paths() {
+        assert!(packages(r#"core = { path = "core", dependencies = ["cli"] }"#).is_err());
+        assert!(packages(r#"core = { scopes = ["core"] }"#).is_err());
+    }
+
+    #[test]
+    fn owns_commits_by_scope_or_path() {
+        let core = &packages(r#"core = { path = "./crates/core" }"#).unwrap()[0];
+        assert!(core.owns(&entry("fix(core): x", &["README.md"])));
+        assert!(core.owns(&entry("fix(api, core): x", &[])));
+        assert!(core.owns(&entry("fix: x", &["crates/core/src/lib.rs"])));
+        assert!(!core.owns(&entry("fix: x", &["crates/core-macros/src/lib.rs"])));
+        assert!(Package::root().owns(&entry("fix: x", &[])));
+    }
+
+    #[test]
+    fn tags_name_the_package() {
+        let version = Version { major: 1, minor: 2, patch: 0 };
+        assert_eq!(Package::root().tag(version), "v1.2.0");
+        assert_eq!(release("core", Bump::None).package.tag(version), "core@v1.2.0");
+    }
+
+    #[test]
+    fn cascades_patch_releases_to_dependents() {
+        // cli depends on api, which depends on core
+        let mut releases = vec![
+            release("cli", Bump::None),
+            release("api", Bump::None),
+            release("core", Bump::Minor),
+            release("docs", Bump::None),
+        ];
+        let dependencies = vec![vec!["api".to_string()], vec!["core".to_string()], Vec::new(), Vec::new()];
+        cascade(&mut releases, &dependencies);
+
+        let summary: Vec<(Bump, String, &[String])> = releases
+            .iter()
+            .map(|r| (r.bump, r.next.to_string(), r.updated_dependencies.as_slice()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (Bump::Patch, "1.0.1".to_string(), &["api".to_string()][..]),
+                (Bump::Patch, "1.0.1".to_string(), &["core".to_string()][..]),
+                (Bump::Minor, "1.1.0".to_string(), &[][..]),
+                (Bump::None, "1.0.0".to_string(), &[][..]),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_higher_bumps_of_dependents() {
+        let mut releases = vec![release("cli", Bump::Major), release("core", Bump::Patch)];
+        cascade(&mut releases, &[vec!["core".to_string()], Vec::new()]);
+        assert_eq!(releases[0].bump, Bump::Major);
+        assert_eq!(releases[0].updated_dependencies, ["core"]);
+    }
+
+    /// A workspace in a temporary directory with `core`, published as
+    /// `acme-core`, and `cli` depending on it as written in `cli_manifest`.
+    fn workspace(name: &str, cli_manifest: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("convinci-{}-{}", name, std::process::id()));
+        for (path, manifest) in [
+            ("crates/core", "[package]\nname = \"acme-core\"\nversion = \"1.0.0\"\n"),
+            ("crates/cli", cli_manifest),
+        ] {
+            fs::create_dir_all(root.join(path)).unwrap();
+            fs::write(root.join(path).join("Cargo.toml"), manifest).unwrap();
+        }
+        root
+    }
+
+    fn cascaded_releases() -> Vec<PackageRelease> {
+        let mut cli = release("cli", Bump::Patch);
+        cli.package.path = PathBuf::from("crates/cli");
+        cli.updated_dependencies = vec!["core".to_string()];
+        let mut core = release("core", Bump::Minor);
+        core.package.path = PathBuf::from("crates/core");
+        vec![cli, core]
+    }
+
+    #[test]
+    fn rewrites_requirements_on_the_dependency_crate() {
+        let root = workspace(
+            "requirements",
+            r#"[package]
+name = "acme-cli"
+version = "1.0.0"
+
+[dependencies]
+acme-core = { path = "../core", version = "^1.0.0" }
+serde = { version = "1.0.0" }
+
+[dev-dependencies]
+core2 = { package = "acme-core", version = "=1.0.0" }
+
+[build-dependencies.acme-core]
+path = "../core"
+version = "1.0.0"
+"#,
+        );
+        let releases = cascaded_releases();
+        assert!(write_manifest(&releases[0], &releases, &root).unwrap());
+        let manifest = fs::read_to_string(root.join("crates/cli/Cargo.toml")).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(
+            manifest,
+            r#"[package]
+name = "acme-cli"
+version = "1.0.1"
+
+[dependencies]
+acme-core = { path = "../core", version = "^1.1.0" }
+serde = { version = "1.0.0" }
+
+[dev-dependencies]
+core2 = { package = "acme-core", version = "=1.1.0" }
+
+[build-dependencies.acme-core]
+path = "../core"
+version = "1.1.0"
+"#
+        );
+    }
+
+    #[test]
+    fn fails_on_requirements_it_cannot_rewrite() {
+        let cli = "[package]\nname = \"acme-cli\"\nversion = \"1.0.0\"\n\n[dependencies]\nacme-core = { path = '../core', version = '1.0.0' }\n";
+        let root = workspace("unreadable", cli);
+        let releases = cascaded_releases();
+        let error = write_manifest(&releases[0], &releases, &root).unwrap_err().to_string();
+        let manifest = fs::read_to_string(root.join("crates/cli/Cargo.toml")).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert!(error.starts_with("Couldn't update the version requirement of `acme-core`"), "{}", error);
+        assert_eq!(manifest, cli);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::commit::ConventionalCommit;
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::sync::LazyLock;
+use toml::{Table, Value};
+
+static PLACEHOLDER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{\s*([A-Za-z0-9_-]+)\s*\}\}").unwrap());
+
+/// A named commit template from the `[presets]` table.
+///
+/// Text fields may contain `{{name}}` placeholders that are asked for before
+/// the commit is generated.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Preset {
+    pub name: String,
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub commit_type: String,
+    pub scope: Option<String>,
+    pub description: String,
+    pub body: Option<String>,
+}
+
+impl Preset {
+    fn fields(&self) -> impl Iterator<Item = &str> {
+        [
+            self.scope.as_deref(),
+            Some(self.description.as_str()),
+            self.body.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Placeholder names in order of first appearance.
+    pub fn placeholders(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for field in self.fields() {
+            for captures in PLACEHOLDER.captures_iter(field) {
+                let name = captures[1].to_string();
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        names
+    }
+
+    /// Builds the commit, replacing placeholders with the given values.
+    pub fn apply(&self, values: &[(String, String)]) -> ConventionalCommit {
+        let fill = |text: &str| {
+            PLACEHOLDER
+                .replace_all(text, |captures: &regex::Captures| {
+                    values
+                        .iter()
+                        .find(|(name, _)| *name == captures[1])
+                        .map(|(_, value)| value.clone())
+                        .unwrap_or_default()
+                })
+                .trim()
+                .to_string()
+        };
+
+        ConventionalCommit {
+            commit_type: self.commit_type.clone(),
+            scope: self.scope.as_deref().map(fill).filter(|s| !s.is_empty()),
+            description: fill(&self.description),
+            body: self.body.as_deref().map(fill).filter(|b| !b.is_empty()),
+            ..ConventionalCommit::default()
+        }
+    }
+}
+
+/// Parses the `[presets]` table, one entry per preset name.
+pub fn parse(table: &Table) -> Result<Vec<Preset>> {
+    table
+        .iter()
+        .map(|(name, value)| {
+            let preset = value
+                .as_table()
+                .with_context(|| format!("Preset `{}` must be a table", name))?;
+            parse_preset(name, preset).with_context(|| format!("Invalid preset `{}`", name))
+        })
+        .collect()
+}
+
+fn parse_preset(name: &str, table: &Table) -> Result<Preset> {
+    let text = |key: &str| -> Result<Option<String>> {
+        match table.get(key) {
+            None => Ok(None),
+            Some(Value::String(s)) => Ok(Some(s.clone())),
+            Some(_) => anyhow::bail!("`{}` must be a string", key),
+        }
+    };
+
+    for key in table.keys() {
+        if !["type", "scope", "description", "body"].contains(&key.as_str()) {
+            anyhow::bail!("Unknown key `{}`", key);
+        }
+    }
+
+    Ok(Preset {
+        name: name.to_string(),
+        commit_type: text("type")?.context("`type` is required")?,
+        scope: text("scope")?,
+        description: text("description")?.unwrap_or_default(),
+        body: text("body")?,
+    })
+}
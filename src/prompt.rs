@@ -0,0 +1,85 @@
+use crate::{commit::ConventionalCommit, config::AppConfig};
+use anyhow::Result;
+use std::io::{self, BufRead, Write};
+
+/// Line-based fallback for when stdin or stdout is not a terminal.
+///
+/// Prompts go to stderr so stdout only carries the resulting message. An empty
+/// answer keeps the current value and `-` clears it. Returns `None` when input
+/// ends before the description was given, and an error when the message
+/// breaks the configured rules.
+pub fn compose(config: &AppConfig, initial: ConventionalCommit) -> Result<Option<ConventionalCommit>> {
+    let Some(commit) = ask_fields(config, initial)? else {
+        return Ok(None);
+    };
+
+    let mut styled = commit.clone();
+    config.style_commit(&mut styled);
+    crate::validate_commit_message(&styled.generate(), config)?;
+    Ok(Some(commit))
+}
+
+fn ask_fields(config: &AppConfig, initial: ConventionalCommit) -> Result<Option<ConventionalCommit>> {
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut commit = initial;
+
+    let mut ask = |question: &str, current: &str| -> Result<Option<String>> {
+        if current.is_empty() {
+            eprint!("{}: ", question);
+        } else {
+            eprint!("{} [{}]: ", question, current);
+        }
+        io::stderr().flush()?;
+
+        match lines.next() {
+            Some(line) => {
+                let line = line?.trim().to_string();
+                Ok(Some(match line.as_str() {
+                    "" => current.to_string(),
+                    "-" => String::new(),
+                    _ => line,
+                }))
+            }
+            None => Ok(None),
+        }
+    };
+
+    eprintln!("Types: {}", config.types.join(", "));
+    let Some(commit_type) = ask("Type", &commit.commit_type)? else {
+        return Ok(None);
+    };
+    commit.commit_type = commit_type;
+
+    let Some(scope) = ask("Scope (optional, - to clear)", commit.scope.as_deref().unwrap_or_default())? else {
+        return Ok(None);
+    };
+    commit.scope = Some(scope).filter(|s| !s.is_empty());
+
+    let Some(description) = ask("Description", &commit.description)? else {
+        return Ok(None);
+    };
+    if description.is_empty() {
+        return Ok(None);
+    }
+    commit.description = description;
+
+    let Some(body) = ask("Body (optional, \\n for new lines, - to clear)", commit.body.as_deref().unwrap_or_default())? else {
+        return Ok(Some(commit));
+    };
+    commit.body = Some(body.replace("\\n", "\n")).filter(|b| !b.is_empty());
+
+    let current = if commit.breaking_change { "y" } else { "n" };
+    let Some(breaking) = ask("Breaking change? (y/n)", current)? else {
+        return Ok(Some(commit));
+    };
+    commit.breaking_change = breaking.eq_ignore_ascii_case("y") || breaking.eq_ignore_ascii_case("yes");
+
+    if commit.breaking_change
+        && let Some(desc) = ask("Breaking change description", &commit.breaking_change_description)?
+    {
+        commit.breaking_change_description = desc;
+    }
+
+    Ok(Some(commit))
+}
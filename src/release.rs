@@ -0,0 +1,317 @@
+use crate::{
+    changelog::{self, LogEntry, Version},
+    git,
+    template::{Template, Value},
+};
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::sync::LazyLock;
+
+static ISSUE_REF: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(^|[\s(])#(\d+)\b").unwrap());
+
+/// Template used when `release-notes` isn't given one.
+pub const DEFAULT_TEMPLATE: &str = r#"## {{ version }}{% if date %} ({{ date }}){% endif %}
+
+{% if breaking %}
+### ⚠ BREAKING CHANGES
+
+{% for commit in breaking %}
+* {% if commit.scope %}**{{ commit.scope }}:** {% endif %}{{ commit.breaking_description }}
+{% endfor %}
+
+{% endif %}
+{% for section in sections %}
+### {{ section.title }}
+
+{% for commit in section.commits %}
+* {% if commit.scope %}**{{ commit.scope }}:** {% endif %}{{ commit.linked_description }}{% for issue in commit.issues %}{% if issue.url %}, closes [#{{ issue.number }}]({{ issue.url }}){% else %}, closes #{{ issue.number }}{% endif %}{% endfor %} ({% if commit.url %}[{{ commit.short_hash }}]({{ commit.url }}){% else %}{{ commit.short_hash }}{% endif %})
+{% endfor %}
+
+{% endfor %}
+{% if contributors %}
+### Contributors
+
+{% for name in contributors %}
+* {{ name }}
+{% endfor %}
+
+{% endif %}
+{% if compare_url %}
+**Full Changelog**: {{ compare_url }}
+{% endif %}
+"#;
+
+/// Web URLs of a repository hosted on a forge, derived from its remote.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repository {
+    pub url: String,
+    gitlab: bool,
+}
+
+impl Repository {
+    /// Understands `git@host:owner/repo.git`, `ssh://git@host/owner/repo` and
+    /// `https://host/owner/repo`. GitLab hosts get their `/-/` paths.
+    pub fn from_remote(remote: &str) -> Option<Self> {
+        let remote = remote.trim();
+        let (host, path) = if let Some(rest) = remote
+            .strip_prefix("https://")
+            .or_else(|| remote.strip_prefix("http://"))
+            .or_else(|| remote.strip_prefix("ssh://"))
+            .or_else(|| remote.strip_prefix("git://"))
+        {
+            let (authority, path) = rest.split_once('/')?;
+            let host = authority.rsplit('@').next()?;
+            // Ports are for the transport, not the web interface
+            let host = if remote.starts_with("http") { host } else { host.split(':').next()? };
+            (host, path)
+        } else {
+            let (authority, path) = remote.split_once(':')?;
+            (authority.rsplit('@').next()?, path)
+        };
+
+        let path = path.trim_end_matches('/').trim_end_matches(".git");
+        if host.is_empty() || path.is_empty() {
+            return None;
+        }
+        let scheme = if remote.starts_with("http://") { "http" } else { "https" };
+        Some(Self {
+            url: format!("{}://{}/{}", scheme, host, path),
+            gitlab: host.contains("gitlab"),
+        })
+    }
+
+    /// The repository of the `origin` remote, if there is one on a forge.
+    pub fn from_origin() -> Option<Self> {
+        git::output(&["remote", "get-url", "origin"])
+            .ok()
+            .and_then(|remote| Self::from_remote(&remote))
+    }
+
+    fn page(&self, kind: &str, target: &str) -> String {
+        let separator = if self.gitlab { "/-/" } else { "/" };
+        format!("{}{}{}/{}", self.url, separator, kind, target)
+    }
+
+    pub fn commit_url(&self, hash: &str) -> String {
+        self.page("commit", hash)
+    }
+
+    pub fn issue_url(&self, number: &str) -> String {
+        self.page("issues", number)
+    }
+
+    pub fn compare_url(&self, from: &str, to: &str) -> String {
+        self.page("compare", &format!("{}...{}", from, to))
+    }
+}
+
+/// The commits of one release and where they come from.
+#[derive(Debug)]
+pub struct Release {
+    pub tag: String,
+    pub previous_tag: Option<String>,
+    /// Date of the tagged commit, or of `HEAD` for an untagged release.
+    pub date: String,
+    pub entries: Vec<LogEntry>,
+    pub repository: Option<Repository>,
+}
+
+impl Release {
+    /// Reads the commits of `tag` since `from`, which defaults to the
+    /// previous tag. A tag that doesn't exist yet is treated as `HEAD`.
+    pub fn read(tag: &str, from: Option<&str>) -> Result<Self> {
+        let tag_ref = format!("refs/tags/{}", tag);
+        let tagged = git::output(&["rev-parse", "-q", "--verify", &tag_ref]).is_ok();
+        let end = if tagged { tag } else { "HEAD" };
+
+        let previous_tag = match from {
+            Some(from) => Some(from.to_string()),
+            None => {
+                let before = if tagged { format!("{}^", tag) } else { "HEAD".to_string() };
+                git::output(&["describe", "--tags", "--abbrev=0", &before]).ok()
+            }
+        };
+        let range = match &previous_tag {
+            Some(previous) => format!("{}..{}", previous, end),
+            None => end.to_string(),
+        };
+
+        Ok(Self {
+            tag: tag.to_string(),
+            date: git::output(&["log", "-1", "--format=%cs", end]).unwrap_or_default(),
+            entries: changelog::read_log(&range)
+                .with_context(|| format!("Failed to read the commits in {}", range))?,
+            previous_tag,
+            repository: Repository::from_origin(),
+        })
+    }
+
+    /// Variables available to release notes templates.
+    pub fn context(&self) -> Value {
+        let sections: Vec<Value> = changelog::group(&self.entries)
+            .iter()
+            .filter(|section| section.shown)
+            .map(|section| {
+                Value::map([
+                    ("type", section.commit_type.as_str().into()),
+                    ("title", section.title.as_str().into()),
+                    ("commits", Value::List(section.entries.iter().map(|e| self.entry(e)).collect())),
+                ])
+            })
+            .collect();
+
+        let mut contributors: Vec<&str> = self.entries.iter().map(|e| e.author.as_str()).collect();
+        contributors.sort_unstable();
+        contributors.dedup();
+
+        let version = Version::parse(&self.tag).map_or_else(|| self.tag.clone(), |v| v.to_string());
+        let compare_url = match (&self.repository, &self.previous_tag) {
+            (Some(repository), Some(previous)) => repository.compare_url(previous, &self.tag),
+            _ => String::new(),
+        };
+
+        Value::map([
+            ("version", version.into()),
+            ("tag", self.tag.as_str().into()),
+            ("previous_tag", self.previous_tag.clone().into()),
+            ("date", self.date.as_str().into()),
+            ("repository", self.repository.as_ref().map(|r| r.url.clone()).into()),
+            ("compare_url", compare_url.into()),
+            ("sections", Value::List(sections)),
+            ("commits", Value::List(self.entries.iter().map(|e| self.entry(e)).collect())),
+            (
+                "breaking",
+                Value::List(changelog::breaking_changes(&self.entries).into_iter().map(|e| self.entry(e)).collect()),
+            ),
+            ("contributors", contributors.into_iter().map(Value::from).collect::<Vec<_>>().into()),
+        ])
+    }
+
+    fn entry(&self, entry: &LogEntry) -> Value {
+        let commit = &entry.commit;
+        let linked_description = match &self.repository {
+            Some(repository) => ISSUE_REF
+                .replace_all(&commit.description, |captures: &regex::Captures| {
+                    let number = &captures[2];
+                    format!("{}[#{}]({})", &captures[1], number, repository.issue_url(number))
+                })
+                .into_owned(),
+            None => commit.description.clone(),
+        };
+        let issues: Vec<Value> = commit
+            .footers
+            .iter()
+            .flat_map(|footer| ISSUE_REF.captures_iter(&footer.value))
+            .map(|captures| {
+                let number = &captures[2];
+                Value::map([
+                    ("number", number.into()),
+                    ("url", self.repository.as_ref().map(|r| r.issue_url(number)).into()),
+                ])
+            })
+            .collect();
+        let breaking_description = match commit.breaking_change_description.trim() {
+            "" => linked_description.clone(),
+            text => text.to_string(),
+        };
+
+        Value::map([
+            ("hash", entry.hash.as_str().into()),
+            ("short_hash", entry.short_hash().into()),
+            ("url", self.repository.as_ref().map(|r| r.commit_url(&entry.hash)).into()),
+            ("author", entry.author.as_str().into()),
+            ("type", commit.commit_type.as_str().into()),
+            ("scope", commit.scope.clone().into()),
+            ("description", commit.description.as_str().into()),
+            ("linked_description", linked_description.into()),
+            ("body", commit.body.clone().into()),
+            ("breaking", commit.breaking_change.into()),
+            ("breaking_description", breaking_description.into()),
+            ("issues", Value::List(issues)),
+        ])
+    }
+
+    /// Renders the notes with `template`, or [`DEFAULT_TEMPLATE`].
+    pub fn render(&self, template: Option<&str>) -> Result<String> {
+        let template = Template::parse(template.unwrap_or(DEFAULT_TEMPLATE)).context("Invalid template")?;
+        template.render(&self.context()).context("Failed to render the template")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commit::ConventionalCommit;
+
+    fn release(messages: &[&str]) -> Release {
+        Release {
+            tag: "v1.1.0".to_string(),
+            previous_tag: Some("v1.0.0".to_string()),
+            date: "2024-05-01".to_string(),
+            entries: messages
+                .iter()
+                .map(|message| LogEntry {
+                    hash: "0123456789abcdef".to_string(),
+                    author: "Jane".to_string(),
+                    commit: ConventionalCommit::parse(message).unwrap(),
+                    files: Vec::new(),
+                })
+                .collect(),
+            repository: Repository::from_remote("git@github.com:acme/app.git"),
+        }
+    }
+
+    #[test]
+    fn reads_web_urls_from_remotes() {
+        let cases = [
+            ("git@github.com:acme/app.git", Some("https://github.com/acme/app")),
+            ("ssh://git@github.com:2222/acme/app.git", Some("https://github.com/acme/app")),
+            ("ssh://git@gitlab.example.com/group/sub/app", Some("https://gitlab.example.com/group/sub/app")),
+            ("https://github.com/acme/app.git\n", Some("https://github.com/acme/app")),
+            ("https://user@git.example.com:8443/acme/app/", Some("https://git.example.com:8443/acme/app")),
+            ("http://git.local/acme/app", Some("http://git.local/acme/app")),
+            ("git://github.com/acme/app.git", Some("https://github.com/acme/app")),
+            ("/srv/git/app.git", None),
+            ("https://github.com", None),
+        ];
+        for (remote, url) in cases {
+            assert_eq!(Repository::from_remote(remote).map(|r| r.url).as_deref(), url, "{}", remote);
+        }
+    }
+
+    #[test]
+    fn uses_gitlab_paths_on_gitlab_hosts() {
+        let github = Repository::from_remote("git@github.com:acme/app.git").unwrap();
+        assert_eq!(github.issue_url("12"), "https://github.com/acme/app/issues/12");
+        assert_eq!(github.compare_url("v1", "v2"), "https://github.com/acme/app/compare/v1...v2");
+
+        let gitlab = Repository::from_remote("git@gitlab.com:group/app.git").unwrap();
+        assert_eq!(gitlab.commit_url("abc"), "https://gitlab.com/group/app/-/commit/abc");
+        assert_eq!(gitlab.issue_url("12"), "https://gitlab.com/group/app/-/issues/12");
+    }
+
+    #[test]
+    fn links_issue_references() {
+        let cases = [
+            ("fix: #12 crash", "[#12](https://github.com/acme/app/issues/12) crash"),
+            ("fix: crash (#12)", "crash ([#12](https://github.com/acme/app/issues/12))"),
+            ("fix: crash in a#12", "crash in a#12"),
+            ("fix: crash in #12a", "crash in #12a"),
+        ];
+        let template = "{% for commit in commits %}{{ commit.linked_description }}{% endfor %}";
+        for (message, linked) in cases {
+            assert_eq!(release(&[message]).render(Some(template)).unwrap(), linked, "{}", message);
+        }
+    }
+
+    #[test]
+    fn renders_the_default_template() {
+        let notes = release(&["feat: add login", "fix: crash\n\nCloses: #7"]).render(None).unwrap();
+
+        assert!(notes.starts_with("## 1.1.0 (2024-05-01)\n"), "{}", notes);
+        assert!(notes.contains(
+            "* crash, closes [#7](https://github.com/acme/app/issues/7) ([0123456](https://github.com/acme/app/commit/0123456789abcdef))"
+        ));
+        assert!(notes.contains("**Full Changelog**: https://github.com/acme/app/compare/v1.0.0...v1.1.0"));
+    }
+}
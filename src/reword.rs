@@ -0,0 +1,166 @@
+use crate::{commit, config, git, history, lint, tui};
+use anyhow::{Context, Result};
+use std::{
+    fs,
+    io::{self, IsTerminal},
+    path::Path,
+    process::Command,
+};
+
+/// Directory in the git directory holding the new messages during the rebase.
+const REWORD_DIR: &str = "CONVINCI_REWORD";
+
+struct Target {
+    hash: String,
+    message: String,
+    violations: Vec<lint::Violation>,
+}
+
+/// Rewrites the messages of the commits in `range` that fail validation,
+/// composing each one in the TUI and applying them with a rebase.
+pub fn run(range: &str, force: bool, inline: bool) -> Result<()> {
+    git::ensure_repository()?;
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        anyhow::bail!("`convinci reword` needs a terminal");
+    }
+    let config = config::load()?;
+    let inline = inline || config.inline;
+
+    let branch = git::current_branch().context("Rewording needs a branch, HEAD is detached")?;
+    if config.protected_branches.contains(&branch) && !force {
+        anyhow::bail!(
+            "`{}` is a protected branch and rewording rewrites its history. Use --force to do it anyway",
+            branch
+        );
+    }
+    if git::has_uncommitted_changes()? {
+        anyhow::bail!("Commit or stash your changes before rewording");
+    }
+
+    let commits = rev_list(&["--reverse", range])?;
+    let mut targets = Vec::new();
+    for hash in &commits {
+        let message = git::output(&["log", "-1", "--format=%B", hash])?;
+        if commit::is_exempt(&message) {
+            continue;
+        }
+        let violations = lint::lint(&message, &config);
+        if !violations.is_empty() {
+            targets.push(Target { hash: hash.clone(), message, violations });
+        }
+    }
+    if targets.is_empty() {
+        println!("✅ All {} commits in {} follow the rules", commits.len(), range);
+        return Ok(());
+    }
+
+    if !force && let Some(pushed) = targets.iter().find(|t| git::is_pushed(&t.hash)) {
+        anyhow::bail!(
+            "{} is already pushed and rewording rewrites it. Use --force to do it anyway",
+            short(&pushed.hash)
+        );
+    }
+
+    // The rebase replays everything from the oldest target up to HEAD
+    let base = git::output(&["rev-parse", "--quiet", "--verify", &format!("{}^", targets[0].hash)]).ok();
+    let replayed = match &base {
+        Some(base) => format!("{}..HEAD", base),
+        None => "HEAD".to_string(),
+    };
+    let todo = rev_list(&["--reverse", &replayed])?;
+    if let Some(target) = targets.iter().find(|t| !todo.contains(&t.hash)) {
+        anyhow::bail!("{} is not on the current branch", short(&target.hash));
+    }
+    if !rev_list(&["--merges", &replayed])?.is_empty() {
+        anyhow::bail!("The commits to reword are followed by merge commits, which can't be replayed");
+    }
+
+    println!("{} of {} commits don't follow the rules:", targets.len(), commits.len());
+    for target in &targets {
+        println!("  {} {}", short(&target.hash), target.message.lines().next().unwrap_or_default());
+        for violation in &target.violations {
+            let violation = violation.to_string();
+            println!("      {}", violation.lines().next().unwrap_or_default());
+        }
+    }
+
+    let mut messages = Vec::new();
+    for (i, target) in targets.iter().enumerate() {
+        let mut app = tui::App::new(config.clone());
+        app.prefill(commit::ConventionalCommit::guess(&target.message));
+        app.progress = Some(format!("reword {}/{} {}", i + 1, targets.len(), short(&target.hash)));
+        app.history = history::entries();
+        crate::run_tui(&mut app, inline, None)?;
+
+        if !app.should_confirm {
+            println!("Reword cancelled, no commit was changed");
+            return Ok(());
+        }
+        app.config.style_commit(&mut app.commit);
+        messages.push((target.hash.clone(), app.commit.generate()));
+    }
+
+    apply(base.as_deref(), &todo, &messages)?;
+    println!("✅ Reworded {} commits", messages.len());
+    Ok(())
+}
+
+fn rev_list(args: &[&str]) -> Result<Vec<String>> {
+    let mut command = vec!["rev-list"];
+    command.extend(args);
+    Ok(git::output(&command)?.lines().map(str::to_string).collect())
+}
+
+fn short(hash: &str) -> &str {
+    &hash[..hash.len().min(7)]
+}
+
+/// Replays `commits` onto `base` (the root when `None`), amending the
+/// message of each commit in `messages` right after it is picked.
+fn apply(base: Option<&str>, commits: &[String], messages: &[(String, String)]) -> Result<()> {
+    let dir = git::git_path(REWORD_DIR)?;
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    // The rebase runs its commands from the work tree root
+    let dir = fs::canonicalize(&dir)?;
+
+    let mut todo = String::new();
+    for hash in commits {
+        todo.push_str(&format!("pick {}\n", hash));
+        if let Some((_, message)) = messages.iter().find(|(h, _)| h == hash) {
+            let path = dir.join(hash);
+            fs::write(&path, message).with_context(|| format!("Failed to write {}", path.display()))?;
+            todo.push_str(&format!(
+                "exec git commit --quiet --amend --allow-empty --no-verify --file {}\n",
+                quote(&path)
+            ));
+        }
+    }
+    let todo_path = dir.join("git-rebase-todo");
+    fs::write(&todo_path, todo).with_context(|| format!("Failed to write {}", todo_path.display()))?;
+
+    // Git runs the sequence editor with the todo file as argument
+    let mut command = Command::new("git");
+    command
+        .args(["rebase", "--interactive", "--quiet"])
+        .env("GIT_SEQUENCE_EDITOR", format!("cp {}", quote(&todo_path)));
+    match base {
+        Some(base) => command.arg(base),
+        None => command.arg("--root"),
+    };
+    let status = command.status().context("Failed to execute git rebase");
+
+    let succeeded = matches!(status, Ok(status) if status.success());
+    if !succeeded {
+        let _ = Command::new("git").args(["rebase", "--abort"]).status();
+    }
+    let _ = fs::remove_dir_all(&dir);
+    if !succeeded {
+        anyhow::bail!("git rebase failed, the branch was left as it was");
+    }
+    Ok(())
+}
+
+/// Single-quotes a path for the shell git runs commands with.
+fn quote(path: &Path) -> String {
+    format!("'{}'", path.display().to_string().replace('\'', r"'\''"))
+}
@@ -0,0 +1,90 @@
+use crate::config::AppConfig;
+
+/// Staged files that belong to the same scope.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileGroup {
+    /// `None` for files outside every configured path.
+    pub scope: Option<String>,
+    pub files: Vec<String>,
+}
+
+/// Groups files by [`AppConfig::scope_for_path`], in order of first
+/// appearance, with unmapped files last.
+pub fn group_files(files: &[String], config: &AppConfig) -> Vec<FileGroup> {
+    let mut groups: Vec<FileGroup> = Vec::new();
+    let mut unmapped = Vec::new();
+
+    for file in files {
+        let Some(scope) = config.scope_for_path(file) else {
+            unmapped.push(file.clone());
+            continue;
+        };
+        match groups.iter_mut().find(|g| g.scope.as_deref() == Some(scope)) {
+            Some(group) => group.files.push(file.clone()),
+            None => groups.push(FileGroup {
+                scope: Some(scope.to_string()),
+                files: vec![file.clone()],
+            }),
+        }
+    }
+
+    if !unmapped.is_empty() {
+        groups.push(FileGroup { scope: None, files: unmapped });
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config;
+
+    fn config() -> AppConfig {
+        let table = r#"
+            [scope-paths]
+            api = "src"
+            ui = ["./src/ui/", "assets/"]
+
+            [packages.core]
+            path = "./crates/core/"
+            scopes = ["core", "parser"]
+        "#;
+        config::parse_table(&table.parse().unwrap()).unwrap()
+    }
+
+    fn group(scope: Option<&str>, files: &[&str]) -> FileGroup {
+        FileGroup {
+            scope: scope.map(str::to_string),
+            files: files.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+
+    fn group_paths(files: &[&str]) -> Vec<FileGroup> {
+        let files: Vec<String> = files.iter().map(|f| f.to_string()).collect();
+        group_files(&files, &config())
+    }
+
+    #[test]
+    fn groups_files_by_the_longest_matching_path() {
+        assert_eq!(
+            group_paths(&["src/ui/app.rs", "src/lib.rs", "crates/core/src/lib.rs", "assets/logo.svg", "src/uix.rs"]),
+            [
+                group(Some("ui"), &["src/ui/app.rs", "assets/logo.svg"]),
+                group(Some("api"), &["src/lib.rs", "src/uix.rs"]),
+                group(Some("core"), &["crates/core/src/lib.rs"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn puts_unmapped_files_last() {
+        assert_eq!(
+            group_paths(&["README.md", "src/lib.rs", "crates/core-macros/lib.rs"]),
+            [
+                group(Some("api"), &["src/lib.rs"]),
+                group(None, &["README.md", "crates/core-macros/lib.rs"]),
+            ]
+        );
+        assert_eq!(group_paths(&[]), []);
+    }
+}
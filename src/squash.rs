@@ -0,0 +1,121 @@
+//! Messages for squash merges, where the pull request title becomes the
+//! commit header.
+
+use crate::{
+    changelog::{LogEntry, SECTIONS},
+    commit::ConventionalCommit,
+};
+
+/// Suggests a squash commit for `entries` (newest first, as read from the
+/// log). The type is the most significant one in [`SECTIONS`] order, the
+/// scope is kept when all commits share it, and the description is the one
+/// of the oldest commit of that type. The body lists every commit and any
+/// breaking change of a constituent commit is carried over.
+pub fn suggest(entries: &[LogEntry]) -> Option<ConventionalCommit> {
+    let rank = |commit_type: &str| {
+        SECTIONS
+            .iter()
+            .position(|(t, _, _)| *t == commit_type)
+            .unwrap_or(SECTIONS.len())
+    };
+    let main = entries
+        .iter()
+        .rev()
+        .min_by_key(|entry| rank(&entry.commit.commit_type))?;
+
+    let first_scope = entries.first()?.commit.scope.clone();
+    let scope = first_scope.filter(|scope| {
+        entries.iter().all(|e| e.commit.scope.as_ref() == Some(scope))
+    });
+
+    let breaking: Vec<&LogEntry> = entries.iter().rev().filter(|e| e.commit.breaking_change).collect();
+    let breaking_change_description = breaking
+        .iter()
+        .map(|e| match e.commit.breaking_change_description.trim() {
+            "" => e.commit.description.clone(),
+            text => text.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    // A single commit needs no list of itself
+    let body = (entries.len() > 1).then(|| {
+        entries
+            .iter()
+            .rev()
+            .map(|e| format!("* {}", e.commit.generate().lines().next().unwrap_or_default()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+
+    Some(ConventionalCommit {
+        commit_type: main.commit.commit_type.clone(),
+        scope,
+        description: main.commit.description.clone(),
+        body,
+        breaking_change: !breaking.is_empty(),
+        breaking_change_description,
+        ..ConventionalCommit::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Entries read from messages newest first, skipping the ones that
+    /// don't parse like the log does.
+    fn suggest_for(messages: &[&str]) -> Option<ConventionalCommit> {
+        let entries: Vec<LogEntry> = messages
+            .iter()
+            .filter_map(|message| ConventionalCommit::parse(message).ok())
+            .map(|commit| LogEntry { hash: String::new(), author: String::new(), commit, files: Vec::new() })
+            .collect();
+        suggest(&entries)
+    }
+
+    #[test]
+    fn takes_the_most_significant_type() {
+        let commit = suggest_for(&[
+            "docs(api): update the guide",
+            "feat(api): add search",
+            "fix(api): handle empty queries",
+            "feat(api): add filters",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            commit.generate(),
+            "feat(api): add filters\n\n\
+             * feat(api): add filters\n\
+             * fix(api): handle empty queries\n\
+             * feat(api): add search\n\
+             * docs(api): update the guide"
+        );
+    }
+
+    #[test]
+    fn drops_mixed_scopes() {
+        assert_eq!(suggest_for(&["fix(ui): x", "fix(api): y"]).unwrap().scope, None);
+        assert_eq!(suggest_for(&["fix: x", "fix(api): y"]).unwrap().scope, None);
+        assert_eq!(suggest_for(&["fix(api): x"]).unwrap().body, None);
+    }
+
+    #[test]
+    fn carries_breaking_changes_over() {
+        let commit = suggest_for(&["fix!: drop the v1 flag", "feat: x\n\nBREAKING CHANGE: the config moved", "chore: y"]).unwrap();
+
+        assert!(commit.breaking_change);
+        assert_eq!(commit.commit_type, "feat");
+        assert_eq!(commit.breaking_change_description, "the config moved\ndrop the v1 flag");
+    }
+
+    #[test]
+    fn skips_exempt_and_non_conventional_commits() {
+        let commit = suggest_for(&["Merge branch 'main'", "fixup! feat: x", "Update README", "fix: y"]).unwrap();
+        assert_eq!(commit.generate(), "fix: y");
+
+        assert!(suggest_for(&["Merge branch 'main'", "Update README"]).is_none());
+        assert!(suggest_for(&[]).is_none());
+    }
+}
@@ -0,0 +1,218 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::path::Path;
+use toml::Value;
+
+/// Built-in `(type, scope, paths)` rules, checked in order.
+const DEFAULT_RULES: [(&str, Option<&str>, &[&str]); 4] = [
+    (
+        "ci",
+        None,
+        &[".github/workflows/", ".github/actions/", ".gitlab-ci.yml", ".circleci/", ".travis.yml", "Jenkinsfile"],
+    ),
+    ("docs", None, &["*.md", "*.mdx", "*.rst", "*.adoc", "docs/", "doc/"]),
+    (
+        "test",
+        None,
+        &["tests/", "test/", "spec/", "__tests__/", "*_test.*", "*.test.*", "*_spec.*", "*.spec.*", "test_*.py"],
+    ),
+    (
+        "build",
+        Some("deps"),
+        &[
+            "Cargo.toml", "Cargo.lock", "package.json", "package-lock.json", "yarn.lock", "pnpm-lock.yaml",
+            "go.mod", "go.sum", "Gemfile", "Gemfile.lock", "pyproject.toml", "poetry.lock", "requirements*.txt",
+            "composer.json", "composer.lock",
+        ],
+    ),
+];
+
+/// Suggests a type (and scope) when every staged file matches one of `paths`.
+///
+/// A path ending with `/` matches files in such a directory at any depth, a
+/// path without `/` matches file names, and other paths match from the root.
+/// `*` stands for any characters but `/` and `**` for any characters.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypeRule {
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub commit_type: String,
+    pub scope: Option<String>,
+    pub paths: Vec<String>,
+}
+
+impl TypeRule {
+    fn matches(&self, file: &str) -> bool {
+        self.paths.iter().any(|pattern| matches(pattern, file))
+    }
+}
+
+pub fn default_rules() -> Vec<TypeRule> {
+    DEFAULT_RULES
+        .iter()
+        .map(|(commit_type, scope, paths)| TypeRule {
+            commit_type: commit_type.to_string(),
+            scope: scope.map(str::to_string),
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+        })
+        .collect()
+}
+
+/// The first rule matching all `files`, `None` when nothing is staged.
+pub fn suggest<'a>(files: &[String], rules: &'a [TypeRule]) -> Option<&'a TypeRule> {
+    if files.is_empty() {
+        return None;
+    }
+    rules
+        .iter()
+        .find(|rule| files.iter().all(|file| rule.matches(file)))
+}
+
+fn matches(pattern: &str, file: &str) -> bool {
+    if let Some(directory) = pattern.strip_suffix('/') {
+        let Some(parent) = Path::new(file).parent() else {
+            return false;
+        };
+        let directory = glob(directory);
+        // Any run of directories, `docs` matches `docs/` and `crates/a/docs/`
+        let parent: Vec<&str> = parent.iter().filter_map(|c| c.to_str()).collect();
+        let depth = directory.as_str().matches('/').count() + 1;
+        return parent
+            .windows(depth)
+            .any(|window| directory.is_match(&window.join("/")));
+    }
+
+    if pattern.contains('/') {
+        glob(pattern).is_match(file)
+    } else {
+        let name = file.rsplit('/').next().unwrap_or(file);
+        glob(pattern).is_match(name)
+    }
+}
+
+fn glob(pattern: &str) -> Regex {
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                regex.push_str(".*");
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).expect("escaped glob")
+}
+
+/// Parses `type-rules`, an array of `{ type, scope, paths }` tables that
+/// replaces the built-in rules. An empty array disables suggestions.
+pub fn parse(value: &Value) -> Result<Vec<TypeRule>> {
+    let rules = value.as_array().context("`type-rules` must be an array of tables")?;
+    rules
+        .iter()
+        .enumerate()
+        .map(|(i, rule)| parse_rule(rule).with_context(|| format!("Invalid entry {} of `type-rules`", i + 1)))
+        .collect()
+}
+
+fn parse_rule(value: &Value) -> Result<TypeRule> {
+    let table = value.as_table().context("Each rule must be a table")?;
+    let mut rule = TypeRule {
+        commit_type: String::new(),
+        scope: None,
+        paths: Vec::new(),
+    };
+
+    for (key, value) in table {
+        match key.as_str() {
+            "type" => rule.commit_type = value.as_str().context("`type` must be a string")?.to_string(),
+            "scope" => rule.scope = Some(value.as_str().context("`scope` must be a string")?.to_string()),
+            "paths" => rule.paths = crate::config::string_list(value, "paths")?,
+            _ => anyhow::bail!("Unknown key `{}`", key),
+        }
+    }
+
+    if rule.commit_type.is_empty() || rule.paths.is_empty() {
+        anyhow::bail!("`type` and `paths` are required");
+    }
+    Ok(rule)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|p| p.to_string()).collect()
+    }
+
+    fn suggested(paths: &[&str]) -> Option<(String, Option<String>)> {
+        suggest(&files(paths), &default_rules()).map(|rule| (rule.commit_type.clone(), rule.scope.clone()))
+    }
+
+    #[test]
+    fn directories_match_at_any_depth() {
+        assert!(matches("docs/", "docs/guide.md"));
+        assert!(matches("docs/", "crates/core/docs/api/index.html"));
+        assert!(!matches("docs/", "docs"));
+        assert!(!matches("docs/", "src/docs.rs"));
+        assert!(matches(".github/workflows/", ".github/workflows/ci.yml"));
+        assert!(!matches(".github/workflows/", ".github/dependabot.yml"));
+    }
+
+    #[test]
+    fn names_match_the_file_name() {
+        assert!(matches("*.md", "README.md"));
+        assert!(matches("*.md", "crates/core/CHANGELOG.md"));
+        assert!(matches("Cargo.toml", "crates/core/Cargo.toml"));
+        assert!(matches("requirements*.txt", "requirements-dev.txt"));
+        assert!(matches("test_*.py", "pkg/test_api.py"));
+        assert!(!matches("*.md", "README.mdx"));
+        assert!(!matches("Cargo.toml", "Cargo.toml.orig"));
+    }
+
+    #[test]
+    fn paths_match_from_the_root() {
+        assert!(matches("src/*.rs", "src/main.rs"));
+        assert!(!matches("src/*.rs", "src/ui/app.rs"));
+        assert!(matches("src/**.rs", "src/ui/app.rs"));
+        assert!(matches("src/?.rs", "src/a.rs"));
+        assert!(!matches("src/*.rs", "crates/src/main.rs"));
+        assert!(!matches("a+b/*.rs", "aab/x.rs"));
+    }
+
+    #[test]
+    fn suggests_when_every_file_matches() {
+        assert_eq!(suggested(&["README.md", "docs/setup.md"]), Some(("docs".to_string(), None)));
+        assert_eq!(
+            suggested(&["Cargo.toml", "Cargo.lock"]),
+            Some(("build".to_string(), Some("deps".to_string())))
+        );
+        assert_eq!(suggested(&["tests/cli.rs", "src/app.test.ts"]), Some(("test".to_string(), None)));
+        // A workflow's README is CI, the first rule wins
+        assert_eq!(suggested(&[".github/workflows/README.md"]), Some(("ci".to_string(), None)));
+        assert_eq!(suggested(&["README.md", "src/main.rs"]), None);
+        assert_eq!(suggested(&[]), None);
+    }
+
+    #[test]
+    fn parses_configured_rules() {
+        let table: toml::Table = r#"
+            rules = [{ type = "chore", scope = "release", paths = ["CHANGELOG.md"] }]
+            missing = [{ type = "docs" }]
+            unknown = [{ type = "docs", paths = ["*.md"], when = "always" }]
+            "#
+        .parse()
+        .unwrap();
+
+        let rules = parse(&table["rules"]).unwrap();
+        assert_eq!(suggest(&files(&["CHANGELOG.md"]), &rules), rules.first());
+        assert!(suggest(&files(&["README.md"]), &rules).is_none());
+        assert!(parse(&table["missing"]).is_err());
+        assert!(parse(&table["unknown"]).is_err());
+    }
+}
@@ -0,0 +1,320 @@
+//! A minimal template language for release notes.
+//!
+//! - `{{ commit.scope }}` prints a value, following `.` into maps
+//! - `{% if breaking %}...{% else %}...{% endif %}`, with an optional `not`
+//! - `{% for commit in commits %}...{% endfor %}`
+//! - `{# comments #}`
+//!
+//! Empty text, `false`, empty lists and missing values are false in
+//! conditions. A line holding only a block tag or a comment is removed
+//! entirely, so tags can sit on their own lines without leaving blank ones.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::{collections::BTreeMap, sync::LazyLock};
+
+static TAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)\{\{(.*?)\}\}|\{%(.*?)%\}|\{#.*?#\}").unwrap());
+
+/// Data available to a template.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Text(String),
+    Bool(bool),
+    List(Vec<Value>),
+    Map(BTreeMap<String, Value>),
+}
+
+impl Value {
+    pub fn map<const N: usize>(entries: [(&str, Value); N]) -> Self {
+        Value::Map(entries.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+    }
+
+    fn is_truthy(&self) -> bool {
+        match self {
+            Value::Text(text) => !text.is_empty(),
+            Value::Bool(b) => *b,
+            Value::List(items) => !items.is_empty(),
+            Value::Map(entries) => !entries.is_empty(),
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(text: &str) -> Self {
+        Value::Text(text.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(text: String) -> Self {
+        Value::Text(text)
+    }
+}
+
+impl From<Option<String>> for Value {
+    fn from(text: Option<String>) -> Self {
+        Value::Text(text.unwrap_or_default())
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(items: Vec<T>) -> Self {
+        Value::List(items.into_iter().map(Into::into).collect())
+    }
+}
+
+#[derive(Debug)]
+enum Node {
+    Text(String),
+    Print(String),
+    If {
+        condition: String,
+        negate: bool,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    For {
+        name: String,
+        list: String,
+        body: Vec<Node>,
+    },
+}
+
+#[derive(Debug)]
+enum Token {
+    Text(String),
+    Print(String),
+    Block(String),
+}
+
+/// A parsed template, rendered with [`Template::render`].
+#[derive(Debug)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut tokens = lex(source).into_iter().peekable();
+        let (nodes, end) = parse_nodes(&mut tokens)?;
+        if let Some(tag) = end {
+            anyhow::bail!("Unexpected `{{% {} %}}`", tag);
+        }
+        Ok(Self { nodes })
+    }
+
+    /// Renders the template with `context`, which must be a [`Value::Map`].
+    pub fn render(&self, context: &Value) -> Result<String> {
+        let mut out = String::new();
+        render_nodes(&self.nodes, context, &mut Vec::new(), &mut out)?;
+        Ok(out)
+    }
+}
+
+fn lex(source: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut last = 0;
+
+    for captures in TAG.captures_iter(source) {
+        let whole = captures.get(0).unwrap();
+        let (mut start, mut end) = (whole.start(), whole.end());
+
+        if captures.get(1).is_none() {
+            // Block tags and comments alone on their line take the line with them
+            let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+            let line_end = source[end..].find('\n').map_or(source.len(), |i| end + i + 1);
+            if line_start >= last
+                && source[line_start..start].trim().is_empty()
+                && source[end..line_end].trim().is_empty()
+            {
+                start = line_start;
+                end = line_end;
+            }
+        }
+
+        if start > last {
+            tokens.push(Token::Text(source[last..start].to_string()));
+        }
+        if let Some(expression) = captures.get(1) {
+            tokens.push(Token::Print(expression.as_str().trim().to_string()));
+        } else if let Some(block) = captures.get(2) {
+            tokens.push(Token::Block(block.as_str().trim().to_string()));
+        }
+        last = end;
+    }
+
+    if last < source.len() {
+        tokens.push(Token::Text(source[last..].to_string()));
+    }
+    tokens
+}
+
+/// Parses nodes until a closing tag (`else`, `endif`, `endfor`), which is
+/// returned for the caller to check.
+fn parse_nodes(
+    tokens: &mut std::iter::Peekable<std::vec::IntoIter<Token>>,
+) -> Result<(Vec<Node>, Option<String>)> {
+    let mut nodes = Vec::new();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Text(text) => nodes.push(Node::Text(text)),
+            Token::Print(path) => nodes.push(Node::Print(path)),
+            Token::Block(block) => {
+                let words: Vec<&str> = block.split_whitespace().collect();
+                match words.as_slice() {
+                    ["if", "not", condition] | ["if", condition] => {
+                        let negate = words.len() == 3;
+                        let condition = condition.to_string();
+                        let (then, end) = parse_nodes(tokens)?;
+                        let otherwise = match end.as_deref() {
+                            Some("endif") => Vec::new(),
+                            Some("else") => match parse_nodes(tokens)? {
+                                (otherwise, Some(end)) if end == "endif" => otherwise,
+                                _ => anyhow::bail!("Missing `{{% endif %}}` for `{{% {} %}}`", block),
+                            },
+                            _ => anyhow::bail!("Missing `{{% endif %}}` for `{{% {} %}}`", block),
+                        };
+                        nodes.push(Node::If { condition, negate, then, otherwise });
+                    }
+                    ["for", name, "in", list] => {
+                        let (name, list) = (name.to_string(), list.to_string());
+                        let body = match parse_nodes(tokens)? {
+                            (body, Some(end)) if end == "endfor" => body,
+                            _ => anyhow::bail!("Missing `{{% endfor %}}` for `{{% {} %}}`", block),
+                        };
+                        nodes.push(Node::For { name, list, body });
+                    }
+                    ["else"] | ["endif"] | ["endfor"] => return Ok((nodes, Some(block))),
+                    _ => anyhow::bail!("Unknown tag `{{% {} %}}`", block),
+                }
+            }
+        }
+    }
+
+    Ok((nodes, None))
+}
+
+fn lookup<'a>(path: &str, context: &'a Value, scope: &[(&str, &'a Value)]) -> Option<&'a Value> {
+    let mut parts = path.split('.');
+    let first = parts.next()?;
+    let mut value = scope
+        .iter()
+        .rev()
+        .find(|(name, _)| *name == first)
+        .map(|(_, value)| *value)
+        .or_else(|| match context {
+            Value::Map(entries) => entries.get(first),
+            _ => None,
+        })?;
+
+    for part in parts {
+        value = match value {
+            Value::Map(entries) => entries.get(part)?,
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+fn render_nodes<'a>(
+    nodes: &'a [Node],
+    context: &'a Value,
+    scope: &mut Vec<(&'a str, &'a Value)>,
+    out: &mut String,
+) -> Result<()> {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Print(path) => {
+                match lookup(path, context, scope).with_context(|| format!("Unknown variable `{}`", path))? {
+                    Value::Text(text) => out.push_str(text),
+                    Value::Bool(b) => out.push_str(&b.to_string()),
+                    _ => anyhow::bail!("`{}` is a list or a map and can't be printed", path),
+                }
+            }
+            Node::If { condition, negate, then, otherwise } => {
+                let truthy = lookup(condition, context, scope).is_some_and(Value::is_truthy);
+                let branch = if truthy != *negate { then } else { otherwise };
+                render_nodes(branch, context, scope, out)?;
+            }
+            Node::For { name, list, body } => {
+                let items = match lookup(list, context, scope) {
+                    Some(Value::List(items)) => items,
+                    Some(_) => anyhow::bail!("`{}` is not a list", list),
+                    None => anyhow::bail!("Unknown variable `{}`", list),
+                };
+                for item in items {
+                    scope.push((name, item));
+                    render_nodes(body, context, scope, out)?;
+                    scope.pop();
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(source: &str, context: &Value) -> String {
+        Template::parse(source).unwrap().render(context).unwrap()
+    }
+
+    #[test]
+    fn prints_values_and_follows_maps() {
+        let context = Value::map([("version", "1.2.0".into()), ("commit", Value::map([("scope", "api".into())]))]);
+        assert_eq!(render("v{{ version }} ({{commit.scope}})", &context), "v1.2.0 (api)");
+    }
+
+    #[test]
+    fn if_else_and_not() {
+        let context = Value::map([("breaking", true.into()), ("notes", Value::List(Vec::new()))]);
+        assert_eq!(render("{% if breaking %}yes{% else %}no{% endif %}", &context), "yes");
+        assert_eq!(render("{% if not breaking %}yes{% else %}no{% endif %}", &context), "no");
+        assert_eq!(render("{% if notes %}notes{% endif %}", &context), "");
+        assert_eq!(render("{% if missing %}set{% endif %}", &context), "");
+    }
+
+    #[test]
+    fn for_loops_shadow_the_context() {
+        let commits = vec![
+            Value::map([("description", "add a".into())]),
+            Value::map([("description", "fix b".into())]),
+        ];
+        let context = Value::map([("commits", Value::List(commits)), ("description", "outer".into())]);
+        let source = "{% for commit in commits %}- {{ commit.description }}\n{% endfor %}{{ description }}";
+        assert_eq!(render(source, &context), "- add a\n- fix b\nouter");
+    }
+
+    #[test]
+    fn comments_render_nothing() {
+        assert_eq!(render("a{# note #}b", &Value::map([])), "ab");
+    }
+
+    #[test]
+    fn lines_holding_only_a_block_tag_are_removed() {
+        let context = Value::map([("items", vec!["a", "b"].into())]);
+        let source = "## Changes\n  {% for item in items %}\n- {{ item }}\n  {% endfor %}\n{# end #}\nDone\n";
+        assert_eq!(render(source, &context), "## Changes\n- a\n- b\nDone\n");
+    }
+
+    #[test]
+    fn reports_unbalanced_and_unknown_tags() {
+        assert!(Template::parse("{% if x %}").is_err());
+        assert!(Template::parse("{% for a in b %}").is_err());
+        assert!(Template::parse("{% endif %}").is_err());
+        assert!(Template::parse("{% include x %}").is_err());
+        let template = Template::parse("{{ missing }}").unwrap();
+        assert!(template.render(&Value::map([])).is_err());
+    }
+}
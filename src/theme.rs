@@ -0,0 +1,256 @@
+use anyhow::{Context, Result};
+use ratatui::style::{Color, Modifier, Style};
+use std::{env, ffi::OsStr, str::FromStr};
+use toml::{Table, Value};
+
+pub const THEME_NAMES: [&str; 4] = ["dark", "light", "high-contrast", "no-color"];
+
+/// Styles used by the TUI, one per kind of element.
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub name: String,
+    /// Regular input text.
+    pub text: Style,
+    /// Borders of fields without focus.
+    pub border: Style,
+    /// Border and labels of the focused field.
+    pub focus: Style,
+    /// Highlighted item in lists.
+    pub selected: Style,
+    /// Hints, disabled fields and secondary information.
+    pub muted: Style,
+    /// Highlights such as locally recorded history entries.
+    pub accent: Style,
+    /// Everything related to breaking changes.
+    pub breaking: Style,
+    /// Wording issues and a header close to or over its maximum length.
+    pub warning: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Self {
+            name: "dark".to_string(),
+            text: Style::default(),
+            border: Style::default(),
+            focus: Style::default().fg(Color::Yellow),
+            selected: Style::default().add_modifier(Modifier::BOLD),
+            muted: Style::default().fg(Color::DarkGray),
+            accent: Style::default().fg(Color::Cyan),
+            breaking: Style::default().fg(Color::Red),
+            warning: Style::default().fg(Color::LightRed).add_modifier(Modifier::UNDERLINED),
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            name: "light".to_string(),
+            focus: Style::default().fg(Color::Blue),
+            muted: Style::default().fg(Color::Gray),
+            accent: Style::default().fg(Color::Magenta),
+            ..Self::dark()
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Self {
+            name: "high-contrast".to_string(),
+            text: Style::default().fg(Color::White),
+            border: Style::default().fg(Color::White),
+            focus: Style::default().fg(Color::LightYellow).add_modifier(Modifier::BOLD),
+            selected: Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED),
+            muted: Style::default().fg(Color::Gray),
+            accent: Style::default().fg(Color::LightCyan).add_modifier(Modifier::BOLD),
+            breaking: Style::default().fg(Color::LightRed).add_modifier(Modifier::BOLD),
+            warning: Style::default().fg(Color::LightRed).add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+        }
+    }
+
+    /// Only text attributes, for terminals or users that don't want colors.
+    pub fn no_color() -> Self {
+        Self {
+            name: "no-color".to_string(),
+            text: Style::default(),
+            border: Style::default(),
+            focus: Style::default().add_modifier(Modifier::BOLD),
+            selected: Style::default().add_modifier(Modifier::REVERSED),
+            muted: Style::default().add_modifier(Modifier::DIM),
+            accent: Style::default().add_modifier(Modifier::ITALIC),
+            breaking: Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            warning: Style::default().add_modifier(Modifier::UNDERLINED),
+        }
+    }
+
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" => Some(Self::high_contrast()),
+            "no-color" => Some(Self::no_color()),
+            _ => None,
+        }
+    }
+
+    /// The theme used when the configuration doesn't name one: `no-color`
+    /// when `NO_COLOR` is set (see <https://no-color.org>), `dark` otherwise.
+    pub fn from_env() -> Self {
+        Self::fallback(env::var_os("NO_COLOR").as_deref())
+    }
+
+    fn fallback(no_color: Option<&OsStr>) -> Self {
+        if no_color.is_some_and(|v| !v.is_empty()) {
+            Self::no_color()
+        } else {
+            Self::dark()
+        }
+    }
+
+    fn slot_mut(&mut self, slot: &str) -> Option<&mut Style> {
+        match slot {
+            "text" => Some(&mut self.text),
+            "border" => Some(&mut self.border),
+            "focus" => Some(&mut self.focus),
+            "selected" => Some(&mut self.selected),
+            "muted" => Some(&mut self.muted),
+            "accent" => Some(&mut self.accent),
+            "breaking" => Some(&mut self.breaking),
+            "warning" => Some(&mut self.warning),
+            _ => None,
+        }
+    }
+}
+
+/// Parses `theme = "light"` or a `[theme]` table with an optional `name`
+/// and style overrides for individual slots.
+pub fn parse(value: &Value) -> Result<Theme> {
+    let table = match value {
+        Value::String(name) => return named(name),
+        Value::Table(table) => table,
+        _ => anyhow::bail!("`theme` must be a theme name or a table"),
+    };
+
+    let mut theme = match table.get("name") {
+        Some(Value::String(name)) => named(name)?,
+        Some(_) => anyhow::bail!("`theme.name` must be a string"),
+        None => Theme::from_env(),
+    };
+    apply_overrides(&mut theme, table)?;
+    Ok(theme)
+}
+
+fn named(name: &str) -> Result<Theme> {
+    Theme::named(name).with_context(|| {
+        format!("Unknown theme `{}`. Available: {}", name, THEME_NAMES.join(", "))
+    })
+}
+
+fn apply_overrides(theme: &mut Theme, table: &Table) -> Result<()> {
+    for (slot, value) in table {
+        if slot == "name" {
+            continue;
+        }
+        let style = theme
+            .slot_mut(slot)
+            .with_context(|| format!("Unknown theme slot `{}`", slot))?;
+        let spec = value
+            .as_str()
+            .with_context(|| format!("`theme.{}` must be a string", slot))?;
+        *style = parse_style(spec).with_context(|| format!("Invalid style for `theme.{}`", slot))?;
+    }
+    Ok(())
+}
+
+/// Parses a style such as `"yellow bold"`, `"#ff8800 on black"` or `"reversed"`.
+///
+/// Words are colors (names, `#rrggbb` or 0-255 indices) and modifiers; a
+/// color after `on` sets the background.
+pub fn parse_style(spec: &str) -> Result<Style> {
+    let mut style = Style::default();
+    let mut words = spec.split_whitespace();
+
+    while let Some(word) = words.next() {
+        let word = word.to_ascii_lowercase();
+        if word == "on" {
+            let color = words.next().context("Expected a color after `on`")?;
+            style = style.bg(parse_color(color)?);
+        } else if let Some(modifier) = modifier(&word) {
+            style = style.add_modifier(modifier);
+        } else {
+            style = style.fg(parse_color(&word)?);
+        }
+    }
+    Ok(style)
+}
+
+fn parse_color(text: &str) -> Result<Color> {
+    Color::from_str(text).map_err(|_| anyhow::anyhow!("Unknown color or modifier `{}`", text))
+}
+
+fn modifier(word: &str) -> Option<Modifier> {
+    Some(match word {
+        "bold" => Modifier::BOLD,
+        "dim" => Modifier::DIM,
+        "italic" => Modifier::ITALIC,
+        "underlined" | "underline" => Modifier::UNDERLINED,
+        "reversed" | "reverse" => Modifier::REVERSED,
+        "crossed-out" | "strikethrough" => Modifier::CROSSED_OUT,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn style(spec: &str) -> Style {
+        parse_style(spec).unwrap()
+    }
+
+    #[test]
+    fn parses_colors_and_modifiers() {
+        assert_eq!(style("red bold"), Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
+        assert_eq!(style("#ff8800 on black"), Style::default().fg(Color::Rgb(255, 136, 0)).bg(Color::Black));
+        assert_eq!(style("Reverse 208"), Style::default().fg(Color::Indexed(208)).add_modifier(Modifier::REVERSED));
+        assert_eq!(style(""), Style::default());
+    }
+
+    #[test]
+    fn rejects_unknown_words() {
+        let error = |spec: &str| parse_style(spec).unwrap_err().to_string();
+
+        assert_eq!(error("red blinky"), "Unknown color or modifier `blinky`");
+        assert_eq!(error("red on"), "Expected a color after `on`");
+    }
+
+    #[test]
+    fn applies_overrides_to_the_named_theme() {
+        let theme = parse(&toml::from_str::<Table>("name = \"light\"\nfocus = \"green\"").unwrap().into()).unwrap();
+
+        assert_eq!(theme.name, "light");
+        assert_eq!(theme.focus, Style::default().fg(Color::Green));
+        assert_eq!(theme.muted, Theme::light().muted);
+    }
+
+    #[test]
+    fn rejects_unknown_themes_and_slots() {
+        let error = |text: &str| format!("{:#}", parse(&toml::from_str::<Table>(text).unwrap().into()).unwrap_err());
+
+        assert_eq!(error("name = \"solar\""), "Unknown theme `solar`. Available: dark, light, high-contrast, no-color");
+        assert_eq!(error("cursor = \"red\""), "Unknown theme slot `cursor`");
+        assert_eq!(error("focus = \"red on\""), "Invalid style for `theme.focus`: Expected a color after `on`");
+    }
+
+    #[test]
+    fn no_color_selects_the_no_color_theme() {
+        assert_eq!(Theme::fallback(Some(OsStr::new("1"))).name, "no-color");
+        assert_eq!(Theme::fallback(Some(OsStr::new(""))).name, "dark");
+        assert_eq!(Theme::fallback(None).name, "dark");
+        assert_eq!(parse(&Value::from("light")).unwrap().name, "light");
+    }
+}
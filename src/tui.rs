@@ -1,8 +1,14 @@
 use crate::{
     commit::ConventionalCommit,
-    config::{COMMIT_SCOPES, COMMIT_TYPES},
+    config::{AppConfig, Doc, NO_SCOPE},
+    history::{self, Entry as HistoryEntry},
+    keymap::Action,
+    preset::Preset,
+    suggest, wording,
+};
+use ratatui::crossterm::event::{
+    KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
 };
-use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{prelude::*, widgets::*};
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -16,61 +22,201 @@ pub enum InputField {
     None,
 }
 
+/// Values being collected for the placeholders of a preset.
+#[derive(Debug)]
+pub struct PresetPrompt {
+    pub preset: Preset,
+    pub values: Vec<(String, String)>,
+    /// Index of the placeholder being typed.
+    pub current: usize,
+}
+
+/// Narrowest list area that still gets a detail pane next to it.
+const DETAIL_MIN_WIDTH: u16 = 80;
+
+/// Smallest height showing the full form, shorter areas show one field at a
+/// time. Inline mode sizes its viewport to this when the terminal allows.
+pub const FULL_FORM_HEIGHT: u16 = 25;
+
+/// Remaining header characters from which the length counter is highlighted.
+const HEADER_LENGTH_WARNING: usize = 10;
+
 #[derive(Debug)]
 pub struct App {
     pub commit: ConventionalCommit,
     pub current_field: InputField,
-    pub config: crate::config::AppConfig,
+    pub config: AppConfig,
     pub should_quit: bool,
     pub list_state_type: ListState,
     pub list_state_scope: ListState,
     pub should_confirm: bool,
     pub single_field_mode: bool,
     pub show_help: bool,
+    /// Autosaved draft offered for restoring on startup.
+    pub draft_offer: Option<ConventionalCommit>,
+    /// Set when the user asked to throw the saved draft away.
+    pub discard_draft: bool,
+    /// Set when the user asked to edit the message in their editor.
+    pub open_editor: bool,
+    /// Recent messages offered by the history browser.
+    pub history: Vec<HistoryEntry>,
+    /// Selection in the history browser, `Some` while it is open.
+    pub history_state: Option<ListState>,
+    /// Selection in the preset list, `Some` while it is open.
+    pub preset_state: Option<ListState>,
+    pub preset_prompt: Option<PresetPrompt>,
+    /// Where each field was drawn in the last frame, for mouse hit testing.
+    pub field_areas: Vec<(InputField, Rect)>,
+    /// Lines the body is scrolled up from its end with the mouse wheel.
+    pub body_scroll_back: u16,
+    /// Progress shown in the footer when composing several commits, like `2/3 api`.
+    pub progress: Option<String>,
+    /// Type guessed from the staged files, marked in the type list.
+    pub suggested_type: Option<String>,
+    /// Wording issues of the description they were found in, checked again
+    /// only when the description changes.
+    description_check: Option<(String, Vec<wording::Issue>)>,
 }
 
-impl Default for App {
-    fn default() -> Self {
+impl App {
+    pub fn new(config: AppConfig) -> Self {
         let mut list_state_type = ListState::default();
         list_state_type.select(Some(0));
 
         let mut list_state_scope = ListState::default();
         list_state_scope.select(Some(0));
 
+        let commit = ConventionalCommit {
+            commit_type: config.types[0].clone(),
+            ..ConventionalCommit::default()
+        };
+
         Self {
-            commit: ConventionalCommit::default(),
+            commit,
             current_field: InputField::Type,
-            config: crate::config::AppConfig::default(),
+            config,
             should_quit: false,
             list_state_type,
             list_state_scope,
             should_confirm: false,
             single_field_mode: false,
             show_help: true,
+            draft_offer: None,
+            discard_draft: false,
+            open_editor: false,
+            history: Vec::new(),
+            history_state: None,
+            preset_state: None,
+            preset_prompt: None,
+            field_areas: Vec::new(),
+            body_scroll_back: 0,
+            progress: None,
+            suggested_type: None,
+            description_check: None,
         }
     }
-}
 
-impl App {
-    pub fn new() -> Self {
-        Self::default()
+    /// Loads an existing commit into the form, adding its type and scope to
+    /// the lists when the configuration does not know them.
+    pub fn prefill(&mut self, commit: ConventionalCommit) {
+        let type_index = match self.config.types.iter().position(|t| *t == commit.commit_type) {
+            Some(index) => index,
+            None => {
+                self.config.types.push(commit.commit_type.clone());
+                self.config.types.len() - 1
+            }
+        };
+        self.list_state_type.select(Some(type_index));
+
+        let scope_index = match &commit.scope {
+            None => 0,
+            Some(scope) => match self.config.scopes.iter().position(|s| s == scope) {
+                Some(index) => index + 1,
+                None => {
+                    self.config.scopes.push(scope.clone());
+                    self.config.scopes.len()
+                }
+            },
+        };
+        self.list_state_scope.select(Some(scope_index));
+
+        self.commit = commit;
+    }
+
+    /// Preselects the type the `type-rules` suggest for `files`, and its
+    /// scope unless one is already set.
+    pub fn suggest_type(&mut self, files: &[String]) {
+        let Some(rule) = suggest::suggest(files, &self.config.type_rules).cloned() else {
+            return;
+        };
+        let mut commit = self.commit.clone();
+        commit.commit_type = rule.commit_type.clone();
+        if commit.scope.is_none() {
+            commit.scope = rule.scope;
+        }
+        self.prefill(commit);
+        self.suggested_type = Some(rule.commit_type);
+    }
+
+    /// Scope list entries, starting with the "no scope" option.
+    fn scope_items(&self) -> Vec<&str> {
+        std::iter::once(NO_SCOPE)
+            .chain(self.config.scopes.iter().map(String::as_str))
+            .collect()
     }
 
     pub fn confirm_commit(&mut self) {
+        if self.commit.breaking_change
+            && self.config.rules.breaking_description_required
+            && self.commit.breaking_change_description.trim().is_empty()
+        {
+            self.focus_field(InputField::BreakingDescription);
+            return;
+        }
         self.should_confirm = true;
         self.should_quit = true;
     }
 
     pub fn handle_key(&mut self, key: KeyEvent) {
-        // Global Ctrl+C shortcut to exit
-        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+        let action = self.config.keys.action(&key);
+        if action == Some(Action::Quit) {
             self.should_quit = true;
             return;
         }
 
-        // Global Ctrl+Enter shortcut to confirm
-        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Enter {
-            self.confirm_commit();
+        if self.draft_offer.is_some() {
+            self.handle_draft_offer(key);
+            return;
+        }
+
+        // Browsing previous messages works from anywhere, even in the preset list
+        if action == Some(Action::History) {
+            self.toggle_history();
+            return;
+        }
+
+        if self.history_state.is_some() {
+            self.handle_history(key);
+            return;
+        }
+
+        if self.preset_prompt.is_some() {
+            self.handle_preset_prompt(key);
+            return;
+        }
+
+        if action == Some(Action::Presets) {
+            self.toggle_presets();
+            return;
+        }
+
+        if self.preset_state.is_some() {
+            self.handle_preset_selection(key);
+            return;
+        }
+
+        if let Some(action) = action {
+            self.run_action(action);
             return;
         }
 
@@ -85,10 +231,240 @@ impl App {
         }
     }
 
+    fn run_action(&mut self, action: Action) {
+        match action {
+            Action::Confirm => self.confirm_commit(),
+            Action::NextField => self.next_field(),
+            Action::PrevField => self.previous_field(),
+            Action::ToggleBreaking => {
+                self.commit.breaking_change = !self.commit.breaking_change;
+                if !self.commit.breaking_change
+                    && self.current_field == InputField::BreakingDescription
+                {
+                    self.focus_field(InputField::BreakingToggle);
+                }
+            }
+            Action::OpenEditor => self.open_editor = true,
+            Action::History => self.toggle_history(),
+            Action::Presets => self.toggle_presets(),
+            Action::Quit => self.should_quit = true,
+        }
+    }
+
+    fn handle_draft_offer(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('r') | KeyCode::Enter => {
+                if let Some(draft) = self.draft_offer.take() {
+                    self.prefill(draft);
+                }
+            }
+            KeyCode::Char('d') => {
+                self.draft_offer = None;
+                self.discard_draft = true;
+            }
+            KeyCode::Esc => self.draft_offer = None,
+            _ => {}
+        }
+    }
+
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) {
+        // Popups are keyboard driven
+        if self.draft_offer.is_some()
+            || self.history_state.is_some()
+            || self.preset_state.is_some()
+            || self.preset_prompt.is_some()
+        {
+            return;
+        }
+
+        let position = Position::new(mouse.column, mouse.row);
+        let Some((field, area)) = self
+            .field_areas
+            .iter()
+            .copied()
+            .find(|(_, area)| area.contains(position))
+        else {
+            return;
+        };
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => self.click_field(field, area, position),
+            MouseEventKind::ScrollDown => self.scroll_field(field, 1),
+            MouseEventKind::ScrollUp => self.scroll_field(field, -1),
+            _ => {}
+        }
+    }
+
+    fn click_field(&mut self, field: InputField, area: Rect, position: Position) {
+        if field == InputField::BreakingDescription && !self.commit.breaking_change {
+            return;
+        }
+        self.focus_field(field);
+
+        // Row inside the list block, skipping the top border
+        let row = position.y.checked_sub(area.y + 1).map(usize::from);
+        match field {
+            InputField::Type => {
+                let index = row.map(|r| r + self.list_state_type.offset());
+                if let Some(index) = index.filter(|&i| i < self.config.types.len()) {
+                    self.select_type(index);
+                }
+            }
+            InputField::Scope => {
+                let index = row.map(|r| r + self.list_state_scope.offset());
+                if let Some(index) = index.filter(|&i| i <= self.config.scopes.len()) {
+                    self.select_scope(index);
+                }
+            }
+            InputField::BreakingToggle => {
+                self.commit.breaking_change = !self.commit.breaking_change;
+            }
+            _ => {}
+        }
+    }
+
+    fn scroll_field(&mut self, field: InputField, delta: i32) {
+        match field {
+            InputField::Type => {
+                let len = self.config.types.len();
+                let selected = self.list_state_type.selected().unwrap_or(0);
+                self.select_type(wrap_index(selected, delta, len));
+            }
+            InputField::Scope => {
+                let len = self.config.scopes.len() + 1;
+                let selected = self.list_state_scope.selected().unwrap_or(0);
+                self.select_scope(wrap_index(selected, delta, len));
+            }
+            InputField::Body => {
+                let lines = self.commit.body.as_deref().unwrap_or_default().lines().count() as u16;
+                self.body_scroll_back = if delta < 0 {
+                    (self.body_scroll_back + 1).min(lines)
+                } else {
+                    self.body_scroll_back.saturating_sub(1)
+                };
+            }
+            _ => {}
+        }
+    }
+
+    fn select_type(&mut self, index: usize) {
+        self.list_state_type.select(Some(index));
+        self.commit.commit_type = self.config.types[index].clone();
+    }
+
+    fn select_scope(&mut self, index: usize) {
+        self.list_state_scope.select(Some(index));
+        self.update_scope_value(index);
+    }
+
+    fn toggle_history(&mut self) {
+        if self.history_state.take().is_none() && !self.history.is_empty() {
+            let mut state = ListState::default();
+            state.select(Some(0));
+            self.history_state = Some(state);
+        }
+    }
+
+    fn handle_history(&mut self, key: KeyEvent) {
+        let len = self.history.len();
+        let Some(state) = &mut self.history_state else {
+            return;
+        };
+        let selected = state.selected().unwrap_or(0);
+
+        match key.code {
+            KeyCode::Down | KeyCode::Char('j') => state.select(Some((selected + 1) % len)),
+            KeyCode::Up | KeyCode::Char('k') => {
+                state.select(Some(if selected == 0 { len - 1 } else { selected - 1 }));
+            }
+            KeyCode::Enter => {
+                self.history_state = None;
+                let message = self.history[selected].message.clone();
+                self.prefill(ConventionalCommit::parse_lenient(&message));
+            }
+            KeyCode::Esc => self.history_state = None,
+            _ => {}
+        }
+    }
+
+    /// Fills the form from a preset, asking for its placeholders first.
+    pub fn start_preset(&mut self, preset: Preset) {
+        let values: Vec<(String, String)> = preset
+            .placeholders()
+            .into_iter()
+            .map(|name| (name, String::new()))
+            .collect();
+
+        if values.is_empty() {
+            self.prefill(preset.apply(&[]));
+            self.focus_field(InputField::Description);
+        } else {
+            self.preset_prompt = Some(PresetPrompt {
+                preset,
+                values,
+                current: 0,
+            });
+        }
+    }
+
+    fn toggle_presets(&mut self) {
+        if self.preset_state.take().is_none() && !self.config.presets.is_empty() {
+            let mut state = ListState::default();
+            state.select(Some(0));
+            self.preset_state = Some(state);
+        }
+    }
+
+    fn handle_preset_selection(&mut self, key: KeyEvent) {
+        let len = self.config.presets.len();
+        let Some(state) = &mut self.preset_state else {
+            return;
+        };
+        let selected = state.selected().unwrap_or(0);
+
+        match key.code {
+            KeyCode::Down | KeyCode::Char('j') => state.select(Some((selected + 1) % len)),
+            KeyCode::Up | KeyCode::Char('k') => {
+                state.select(Some(if selected == 0 { len - 1 } else { selected - 1 }));
+            }
+            KeyCode::Enter => {
+                self.preset_state = None;
+                let preset = self.config.presets[selected].clone();
+                self.start_preset(preset);
+            }
+            KeyCode::Esc => self.preset_state = None,
+            _ => {}
+        }
+    }
+
+    fn handle_preset_prompt(&mut self, key: KeyEvent) {
+        let Some(prompt) = &mut self.preset_prompt else {
+            return;
+        };
+        let value = &mut prompt.values[prompt.current].1;
+
+        match key.code {
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => value.push(c),
+            KeyCode::Backspace => {
+                value.pop();
+            }
+            KeyCode::Enter | KeyCode::Tab if prompt.current + 1 < prompt.values.len() => {
+                prompt.current += 1;
+            }
+            KeyCode::BackTab => prompt.current = prompt.current.saturating_sub(1),
+            KeyCode::Enter => {
+                if let Some(prompt) = self.preset_prompt.take() {
+                    self.prefill(prompt.preset.apply(&prompt.values));
+                    self.focus_field(InputField::Description);
+                }
+            }
+            KeyCode::Esc => self.preset_prompt = None,
+            _ => {}
+        }
+    }
+
     fn handle_no_field(&mut self, key: KeyEvent) {
         match key.code {
-            KeyCode::Tab => self.focus_field(InputField::Type),
-            KeyCode::BackTab => self.focus_field(InputField::BreakingDescription),
             KeyCode::Esc => self.should_quit = true,
             KeyCode::Enter => self.confirm_commit(),
             _ => {}
@@ -112,8 +488,7 @@ impl App {
                     InputField::Type
                 }
             }
-            InputField::BreakingDescription => InputField::Type,
-            _ => InputField::Type,
+            InputField::BreakingDescription | InputField::None => InputField::Type,
         };
     }
 
@@ -125,31 +500,25 @@ impl App {
             InputField::Body => InputField::Description,
             InputField::BreakingToggle => InputField::Body,
             InputField::BreakingDescription => InputField::BreakingToggle,
-            _ => InputField::Type,
+            InputField::None => InputField::BreakingDescription,
         };
     }
 
     fn handle_type_selection(&mut self, key: KeyEvent) {
         let selected = self.list_state_type.selected().unwrap_or(0);
-        let len = COMMIT_TYPES.len();
+        let len = self.config.types.len();
 
         match key.code {
             KeyCode::Down | KeyCode::Char('j') => {
-                let next = (selected + 1) % len;
-                self.list_state_type.select(Some(next));
-                self.commit.commit_type = COMMIT_TYPES[next].to_string();
+                self.select_type((selected + 1) % len);
             }
             KeyCode::Up | KeyCode::Char('k') => {
-                let prev = if selected == 0 { len - 1 } else { selected - 1 };
-                self.list_state_type.select(Some(prev));
-                self.commit.commit_type = COMMIT_TYPES[prev].to_string();
+                self.select_type(if selected == 0 { len - 1 } else { selected - 1 });
             }
-            KeyCode::Tab => self.next_field(),
-            KeyCode::BackTab => self.previous_field(),
             KeyCode::Esc => self.focus_field(InputField::None),
             KeyCode::Enter => self.confirm_commit(),
             // Selection by number (1-9)
-            KeyCode::Char(c) if c.is_digit(10) => {
+            KeyCode::Char(c) if c.is_ascii_digit() => {
                 if let Some(num) = c.to_digit(10) {
                     let idx = if num == 0 {
                         len - 1
@@ -161,8 +530,7 @@ impl App {
                             idx
                         }
                     };
-                    self.list_state_type.select(Some(idx));
-                    self.commit.commit_type = COMMIT_TYPES[idx].to_string();
+                    self.select_type(idx);
                     self.next_field();
                 }
             }
@@ -171,39 +539,28 @@ impl App {
     }
 
     fn handle_scope_selection(&mut self, key: KeyEvent) {
-        let len = COMMIT_SCOPES.len();
+        let len = self.config.scopes.len() + 1;
         let selected = self.list_state_scope.selected().unwrap_or(0);
 
         match key.code {
             KeyCode::Down | KeyCode::Char('j') => {
-                let next = (selected + 1) % len;
-                self.list_state_scope.select(Some(next));
-                self.update_scope_value(next);
+                self.select_scope((selected + 1) % len);
             }
             KeyCode::Up | KeyCode::Char('k') => {
-                let prev = if selected == 0 { len - 1 } else { selected - 1 };
-                self.list_state_scope.select(Some(prev));
-                self.update_scope_value(prev);
+                self.select_scope(if selected == 0 { len - 1 } else { selected - 1 });
             }
-            KeyCode::Tab => self.next_field(),
-            KeyCode::BackTab => self.previous_field(),
             KeyCode::Esc => self.focus_field(InputField::None),
             KeyCode::Enter => self.confirm_commit(),
             // Selection by number (1-9)
-            KeyCode::Char(c) if c.is_digit(10) => {
-                if let Some(num) = c.to_digit(10) {
-                    let idx = (num as usize) - 1;
-                    if idx < len {
-                        self.list_state_scope.select(Some(idx));
-                        self.update_scope_value(idx);
-                        self.next_field();
-                    }
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                if let Some(idx) = c.to_digit(10).and_then(|num| (num as usize).checked_sub(1))
+                    && idx < len
+                {
+                    self.select_scope(idx);
+                    self.next_field();
                 }
             }
-            KeyCode::Backspace => {
-                self.update_scope_value(0);
-                self.list_state_scope.select(Some(0));
-            }
+            KeyCode::Backspace => self.select_scope(0),
             _ => {}
         }
     }
@@ -212,20 +569,25 @@ impl App {
         if index == 0 {
             self.commit.scope = None;
         } else {
-            self.commit.scope = Some(COMMIT_SCOPES[index].to_string());
+            self.commit.scope = Some(self.config.scopes[index - 1].clone());
         }
     }
 
     fn handle_description_input(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.commit.description.push(c);
+                let full = self
+                    .config
+                    .rules
+                    .header_max_length
+                    .is_some_and(|max| self.header_length() >= max);
+                if !(self.config.strict_header_length && full) {
+                    self.commit.description.push(c);
+                }
             }
             KeyCode::Backspace => {
                 self.commit.description.pop();
             }
-            KeyCode::Tab => self.next_field(),
-            KeyCode::BackTab => self.previous_field(),
             KeyCode::Esc => self.focus_field(InputField::None),
             KeyCode::Enter => self.confirm_commit(),
             _ => {}
@@ -233,6 +595,8 @@ impl App {
     }
 
     fn handle_body_input(&mut self, key: KeyEvent) {
+        self.body_scroll_back = 0;
+
         match key.code {
             KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
                 let body = self.commit.body.get_or_insert(String::new());
@@ -247,8 +611,6 @@ impl App {
                 let body = self.commit.body.get_or_insert(String::new());
                 body.push('\n');
             }
-            KeyCode::Tab => self.next_field(),
-            KeyCode::BackTab => self.previous_field(),
             KeyCode::Esc => self.focus_field(InputField::None),
             _ => {}
         }
@@ -259,8 +621,6 @@ impl App {
             KeyCode::Char(' ') => {
                 self.commit.breaking_change = !self.commit.breaking_change;
             }
-            KeyCode::Tab => self.next_field(),
-            KeyCode::BackTab => self.previous_field(),
             KeyCode::Esc => self.focus_field(InputField::None),
             KeyCode::Enter => self.confirm_commit(),
             _ => {}
@@ -275,8 +635,6 @@ impl App {
             KeyCode::Backspace => {
                 self.commit.breaking_change_description.pop();
             }
-            KeyCode::Tab => self.next_field(),
-            KeyCode::BackTab => self.previous_field(),
             KeyCode::Esc => self.focus_field(InputField::BreakingToggle),
             KeyCode::Enter => self.confirm_commit(),
             _ => {}
@@ -285,13 +643,187 @@ impl App {
 
     pub fn render(&mut self, f: &mut Frame) {
         let size = f.area();
-        self.single_field_mode = size.height < 25;
+        self.field_areas.clear();
+        self.single_field_mode = size.height < FULL_FORM_HEIGHT;
+        self.check_description();
 
         if self.single_field_mode {
             self.render_single_field(f);
         } else {
             self.render_full_ui(f);
         }
+
+        if self.history_state.is_some() {
+            self.render_history(f);
+        }
+
+        if self.preset_state.is_some() {
+            self.render_presets(f);
+        }
+
+        if self.preset_prompt.is_some() {
+            self.render_preset_prompt(f);
+        }
+
+        if self.draft_offer.is_some() {
+            self.render_draft_offer(f);
+        }
+    }
+
+    fn render_history(&mut self, f: &mut Frame) {
+        let area = f.area();
+        let area = centered_rect(area, area.width.saturating_sub(8).max(40), area.height.saturating_sub(4));
+
+        let items: Vec<ListItem> = self
+            .history
+            .iter()
+            .map(|entry| {
+                let header = entry.message.lines().next().unwrap_or_default();
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("[{}] ", entry.source.label()),
+                        match entry.source {
+                            history::Source::Local => self.config.theme.accent,
+                            history::Source::Log => self.config.theme.muted,
+                        },
+                    ),
+                    Span::raw(header.to_string()),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title(" History (Enter: Use  Esc: Close) ")
+                    .borders(Borders::ALL)
+                    .border_style(self.config.theme.focus),
+            )
+            .highlight_style(self.config.theme.selected)
+            .highlight_symbol("▶ ");
+
+        f.render_widget(Clear, area);
+        if let Some(state) = &mut self.history_state {
+            f.render_stateful_widget(list, area, state);
+        }
+    }
+
+    fn render_presets(&mut self, f: &mut Frame) {
+        let height = self.config.presets.len() as u16 + 2;
+        let area = centered_rect(f.area(), 60, height);
+
+        let items: Vec<ListItem> = self
+            .config
+            .presets
+            .iter()
+            .map(|preset| {
+                let example = preset.apply(&[]).generate();
+                let example = example.lines().next().unwrap_or_default().to_string();
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{:<16}", preset.name),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(example, self.config.theme.muted),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title(" Presets (Enter: Use  Esc: Close) ")
+                    .borders(Borders::ALL)
+                    .border_style(self.config.theme.focus),
+            )
+            .highlight_style(self.config.theme.selected)
+            .highlight_symbol("▶ ");
+
+        f.render_widget(Clear, area);
+        if let Some(state) = &mut self.preset_state {
+            f.render_stateful_widget(list, area, state);
+        }
+    }
+
+    fn render_preset_prompt(&self, f: &mut Frame) {
+        let Some(prompt) = &self.preset_prompt else {
+            return;
+        };
+
+        let height = prompt.values.len() as u16 + 4;
+        let area = centered_rect(f.area(), 60, height);
+        let label_width = prompt
+            .values
+            .iter()
+            .map(|(name, _)| name.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        let mut lines: Vec<Line> = prompt
+            .values
+            .iter()
+            .enumerate()
+            .map(|(i, (name, value))| {
+                let style = if i == prompt.current {
+                    self.config.theme.focus
+                } else {
+                    self.config.theme.text
+                };
+                Line::from(vec![
+                    Span::styled(format!("{:>width$}: ", name, width = label_width), style),
+                    Span::raw(value.clone()),
+                ])
+            })
+            .collect();
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Enter: Next/Apply  Shift+Tab: Previous  Esc: Cancel",
+            self.config.theme.muted,
+        )));
+
+        let popup = Paragraph::new(lines).block(
+            Block::default()
+                .title(format!(" Preset: {} ", prompt.preset.name))
+                .borders(Borders::ALL)
+                .border_style(self.config.theme.focus),
+        );
+
+        f.render_widget(Clear, area);
+        f.render_widget(popup, area);
+
+        let value = &prompt.values[prompt.current].1;
+        f.set_cursor_position(Position {
+            x: area.x + 1 + (label_width + 2 + value.chars().count()) as u16,
+            y: area.y + 1 + prompt.current as u16,
+        });
+    }
+
+    fn render_draft_offer(&self, f: &mut Frame) {
+        let Some(draft) = &self.draft_offer else {
+            return;
+        };
+
+        let area = centered_rect(f.area(), 60, 7);
+        let header = draft.generate();
+        let header = header.lines().next().unwrap_or_default();
+        let text = vec![
+            Line::from("An unfinished commit message was found:"),
+            Line::from(Span::styled(header.to_string(), Style::default().add_modifier(Modifier::BOLD))),
+            Line::from(""),
+            Line::from("r/Enter: Restore  d: Discard  Esc: Start empty"),
+        ];
+
+        let popup = Paragraph::new(text)
+            .block(
+                Block::default()
+                    .title(" Restore draft? ")
+                    .borders(Borders::ALL)
+                    .border_style(self.config.theme.focus),
+            )
+            .wrap(Wrap { trim: true });
+
+        f.render_widget(Clear, area);
+        f.render_widget(popup, area);
     }
 
     fn render_single_field(&mut self, f: &mut Frame) {
@@ -306,6 +838,11 @@ impl App {
             ])
             .split(area);
 
+        let field_area = match self.current_field {
+            InputField::Type | InputField::Scope => split_detail_area(chunks[1]).0,
+            _ => chunks[1],
+        };
+        self.field_areas.push((self.current_field, field_area));
         self.render_header(f, chunks[0]);
         self.render_active_field(f, chunks[1]);
         self.render_footer(f, chunks[2]);
@@ -325,7 +862,7 @@ impl App {
         let block = Block::default()
             .title(format!(" Conventional Commits Helper - {} ", title))
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Yellow));
+            .border_style(self.config.theme.focus);
 
         let progress = match self.current_field {
             InputField::Type => "Step 1/6",
@@ -357,8 +894,8 @@ impl App {
     }
 
     fn render_full_ui(&mut self, f: &mut Frame) {
-        let type_height = COMMIT_TYPES.len() as u16 + 2;
-        let scope_height = COMMIT_SCOPES.len() as u16 + 2;
+        let type_height = self.config.types.len() as u16 + 2;
+        let scope_height = self.config.scopes.len() as u16 + 3;
 
         // Reduced height for the commit body
         let body_height = 5;
@@ -393,6 +930,16 @@ impl App {
             ])
             .split(f.area());
 
+        let (toggle_area, breaking_description_area) = split_breaking_area(layout[4]);
+        self.field_areas = vec![
+            (InputField::Type, split_detail_area(layout[0]).0),
+            (InputField::Scope, split_detail_area(layout[1]).0),
+            (InputField::Description, layout[2]),
+            (InputField::Body, layout[3]),
+            (InputField::BreakingToggle, toggle_area),
+            (InputField::BreakingDescription, breaking_description_area),
+        ];
+
         self.render_type_field(f, layout[0]);
         self.render_scope_field(f, layout[1]);
         self.render_description_field(f, layout[2]);
@@ -402,7 +949,12 @@ impl App {
     }
 
     fn render_type_field(&mut self, f: &mut Frame, area: Rect) {
-        let items: Vec<ListItem> = COMMIT_TYPES
+        let (area, detail_area) = split_detail_area(area);
+        let width = self.config.types.iter().map(|t| t.chars().count()).max().unwrap_or_default();
+
+        let items: Vec<ListItem> = self
+            .config
+            .types
             .iter()
             .enumerate()
             .map(|(i, t)| {
@@ -412,13 +964,17 @@ impl App {
                     "  "
                 };
 
-                let number = if i == COMMIT_TYPES.len() - 1 {
+                let number = if i == self.config.types.len() - 1 {
                     0
                 } else {
                     i + 1
                 };
 
-                ListItem::new(format!("{}{}. {}", prefix, number, t))
+                let mut item = vec![Span::raw(format!("{}{}. {:<width$}", prefix, number, t))];
+                if self.suggested_type.as_ref() == Some(t) {
+                    item.push(Span::styled("  suggested", self.config.theme.accent));
+                }
+                self.documented_item(item, self.config.type_docs.get(t))
             })
             .collect();
 
@@ -428,19 +984,70 @@ impl App {
                     .title(" Commit Type ")
                     .borders(Borders::ALL)
                     .border_style(if self.current_field == InputField::Type {
-                        Style::default().fg(Color::Yellow)
+                        self.config.theme.focus
                     } else {
-                        Style::default()
+                        self.config.theme.border
                     }),
             )
-            .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+            .highlight_style(self.config.theme.selected)
             .highlight_symbol("▶");
 
         f.render_stateful_widget(list, area, &mut self.list_state_type);
+
+        if let Some(detail_area) = detail_area {
+            let selected = self.list_state_type.selected().unwrap_or(0);
+            let name = &self.config.types[selected];
+            let title = match self.config.emoji.for_type(name).filter(|_| self.config.emoji.enabled) {
+                Some(emoji) => format!("{} {}", emoji, name),
+                None => name.clone(),
+            };
+            self.render_doc_detail(f, detail_area, &title, self.config.type_docs.get(name));
+        }
+    }
+
+    /// List entry spans followed by the muted description, if there is one.
+    fn documented_item(&self, mut spans: Vec<Span<'static>>, doc: Option<&Doc>) -> ListItem<'static> {
+        if let Some(doc) = doc.filter(|d| !d.description.is_empty()) {
+            spans.push(Span::styled(format!("  {}", doc.description), self.config.theme.muted));
+        }
+        ListItem::new(Line::from(spans))
+    }
+
+    /// Description and examples of the highlighted type or scope.
+    fn render_doc_detail(&self, f: &mut Frame, area: Rect, name: &str, doc: Option<&Doc>) {
+        let mut lines = Vec::new();
+        match doc {
+            Some(doc) => {
+                if !doc.description.is_empty() {
+                    lines.push(Line::from(doc.description.clone()));
+                }
+                if !doc.examples.is_empty() {
+                    lines.push(Line::from(""));
+                    lines.push(Line::from(Span::styled("Examples", self.config.theme.muted)));
+                    lines.extend(doc.examples.iter().map(|e| Line::from(format!("  {}", e))));
+                }
+            }
+            None => lines.push(Line::from(Span::styled("No description", self.config.theme.muted))),
+        }
+
+        let detail = Paragraph::new(lines)
+            .style(self.config.theme.text)
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::default()
+                    .title(format!(" {} ", name))
+                    .borders(Borders::ALL)
+                    .border_style(self.config.theme.border),
+            );
+        f.render_widget(detail, area);
     }
 
     fn render_scope_field(&mut self, f: &mut Frame, area: Rect) {
-        let items: Vec<ListItem> = COMMIT_SCOPES
+        let (area, detail_area) = split_detail_area(area);
+        let scopes = self.scope_items();
+        let width = scopes.iter().map(|s| s.chars().count()).max().unwrap_or_default();
+
+        let items: Vec<ListItem> = scopes
             .iter()
             .enumerate()
             .map(|(i, s)| {
@@ -449,7 +1056,8 @@ impl App {
                 } else {
                     "  "
                 };
-                ListItem::new(format!("{}{}. {}", prefix, i + 1, s))
+                let label = format!("{}{}. {:<width$}", prefix, i + 1, s);
+                self.documented_item(vec![Span::raw(label)], self.config.scope_docs.get(*s))
             })
             .collect();
 
@@ -459,35 +1067,115 @@ impl App {
                     .title(" Scope (optional) ")
                     .borders(Borders::ALL)
                     .border_style(if self.current_field == InputField::Scope {
-                        Style::default().fg(Color::Yellow)
+                        self.config.theme.focus
                     } else {
-                        Style::default()
+                        self.config.theme.border
                     }),
             )
-            .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+            .highlight_style(self.config.theme.selected)
             .highlight_symbol("▶");
 
         f.render_stateful_widget(list, area, &mut self.list_state_scope);
+
+        if let Some(detail_area) = detail_area {
+            match self.list_state_scope.selected().filter(|&i| i > 0) {
+                Some(index) => {
+                    let name = &self.config.scopes[index - 1];
+                    self.render_doc_detail(f, detail_area, name, self.config.scope_docs.get(name));
+                }
+                None => {
+                    let doc = Doc {
+                        description: "The change isn't specific to one part of the project".to_string(),
+                        examples: Vec::new(),
+                    };
+                    self.render_doc_detail(f, detail_area, NO_SCOPE, Some(&doc));
+                }
+            }
+        }
+    }
+
+    /// Length of the header the form produces, emoji and `!` included.
+    fn header_length(&self) -> usize {
+        let mut commit = self.commit.clone();
+        self.config.style_commit(&mut commit);
+        commit.generate().lines().next().map_or(0, |header| header.chars().count())
+    }
+
+    /// Runs the wording checks when the description changed since the last frame.
+    fn check_description(&mut self) {
+        let description = &self.commit.description;
+        if self.description_check.as_ref().is_some_and(|(checked, _)| checked == description) {
+            return;
+        }
+        let issues = wording::check(description, &self.config);
+        self.description_check = Some((description.clone(), issues));
+    }
+
+    /// Wording issues of the description. The word being typed is only spell
+    /// checked once it is finished.
+    fn description_issues(&self) -> Vec<&wording::Issue> {
+        let description = &self.commit.description;
+        let typing = self.current_field == InputField::Description
+            && !description.ends_with(|c: char| c.is_whitespace() || c.is_ascii_punctuation());
+        let Some((_, issues)) = &self.description_check else {
+            return Vec::new();
+        };
+        issues
+            .iter()
+            .filter(|issue| !(typing && issue.rule == "spell-check" && issue.span.end == description.len()))
+            .collect()
     }
 
     fn render_description_field(&self, f: &mut Frame, area: Rect) {
-        let input = Paragraph::new(self.commit.description.as_str())
-            .style(Style::default())
-            .block(
-                Block::default()
-                    .title(" Description ")
-                    .borders(Borders::ALL)
-                    .border_style(if self.current_field == InputField::Description {
-                        Style::default().fg(Color::Yellow)
-                    } else {
-                        Style::default()
-                    }),
-            );
+        let description = self.commit.description.as_str();
+        let issues = self.description_issues();
+
+        // Underline the parts issues are about, the messages go in the border
+        let mut spans = Vec::new();
+        let mut last = 0;
+        for issue in &issues {
+            let start = issue.span.start.max(last);
+            if start >= issue.span.end {
+                continue;
+            }
+            spans.push(Span::raw(&description[last..start]));
+            spans.push(Span::styled(&description[start..issue.span.end], self.config.theme.warning));
+            last = issue.span.end;
+        }
+        spans.push(Span::raw(&description[last..]));
+
+        let mut block = Block::default()
+            .title(" Description ")
+            .borders(Borders::ALL)
+            .border_style(if self.current_field == InputField::Description {
+                self.config.theme.focus
+            } else {
+                self.config.theme.border
+            });
+        if let Some(max) = self.config.rules.header_max_length {
+            let length = self.header_length();
+            let (counter, style) = if length > max {
+                (format!(" {} over ", length - max), self.config.theme.warning.add_modifier(Modifier::BOLD))
+            } else if max - length <= HEADER_LENGTH_WARNING {
+                (format!(" {} left ", max - length), self.config.theme.warning)
+            } else {
+                (format!(" {} left ", max - length), self.config.theme.muted)
+            };
+            block = block.title(Line::styled(counter, style).right_aligned());
+        }
+        if !issues.is_empty() {
+            let messages: Vec<&str> = issues.iter().map(|issue| issue.message.as_str()).collect();
+            block = block.title_bottom(Span::styled(format!(" {} ", messages.join(" · ")), self.config.theme.warning));
+        }
+
+        let input = Paragraph::new(Line::from(spans))
+            .style(self.config.theme.text)
+            .block(block);
 
         f.render_widget(input, area);
 
         if self.current_field == InputField::Description {
-            let cursor_x = area.x + self.commit.description.len() as u16 + 1;
+            let cursor_x = area.x + self.commit.description.chars().count() as u16 + 1;
             let cursor_y = area.y + 1;
             f.set_cursor_position(Position {x: cursor_x, y: cursor_y});
         }
@@ -498,18 +1186,20 @@ impl App {
 
         let line_count = body.lines().count() as u16;
         let inner_height = area.height.saturating_sub(2);
-        let offset_y = line_count.saturating_sub(inner_height);
+        let offset_y = line_count
+            .saturating_sub(inner_height)
+            .saturating_sub(self.body_scroll_back);
 
         let input = Paragraph::new(body)
-            .style(Style::default())
+            .style(self.config.theme.text)
             .block(
                 Block::default()
                     .title(" Body (optional) ")
                     .borders(Borders::ALL)
                     .border_style(if self.current_field == InputField::Body {
-                        Style::default().fg(Color::Yellow)
+                        self.config.theme.focus
                     } else {
-                        Style::default()
+                        self.config.theme.border
                     }),
             )
             .scroll((offset_y, 0));
@@ -529,17 +1219,17 @@ impl App {
             .title(" Breaking Change? (SPACE to enable)")
             .borders(Borders::ALL)
             .border_style(if self.current_field == InputField::BreakingToggle {
-                Style::default().fg(Color::Red)
+                self.config.theme.breaking
             } else {
-                Style::default()
+                self.config.theme.border
             });
 
         let paragraph = Paragraph::new(text)
             .block(block)
             .style(if self.commit.breaking_change {
-                Style::default().fg(Color::Red)
+                self.config.theme.breaking
             } else {
-                Style::default()
+                self.config.theme.text
             });
 
         f.render_widget(paragraph, area);
@@ -547,15 +1237,19 @@ impl App {
 
     fn render_breaking_description(&self, f: &mut Frame, area: Rect) {
         let input = Paragraph::new(self.commit.breaking_change_description.as_str())
-            .style(Style::default())
+            .style(self.config.theme.text)
             .block(
                 Block::default()
-                    .title(" Breaking Change Description ")
+                    .title(if self.config.rules.breaking_description_required {
+                        " Breaking Change Description (required) "
+                    } else {
+                        " Breaking Change Description "
+                    })
                     .borders(Borders::ALL)
                     .border_style(if self.current_field == InputField::BreakingDescription {
-                        Style::default().fg(Color::Red)
+                        self.config.theme.breaking
                     } else {
-                        Style::default()
+                        self.config.theme.border
                     }),
             );
 
@@ -570,69 +1264,129 @@ impl App {
     }
 
     fn render_breaking_field(&self, f: &mut Frame, area: Rect) {
-        let layout = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3), // Toggle (always visible)
-                Constraint::Length(3), // Description (always visible)
-            ])
-            .split(area);
+        let (toggle_area, description_area) = split_breaking_area(area);
 
-        self.render_breaking_toggle(f, layout[0]);
+        self.render_breaking_toggle(f, toggle_area);
 
         // Now always render the description, even when it's not active
         if self.commit.breaking_change {
-            self.render_breaking_description(f, layout[1]);
+            self.render_breaking_description(f, description_area);
         } else {
             // When not active, show empty field
             let input = Paragraph::new("")
-                .style(Style::default().fg(Color::DarkGray))
+                .style(self.config.theme.muted)
                 .block(
                     Block::default()
                         .title(" Breaking Change Description (disabled) ")
                         .borders(Borders::ALL)
-                        .border_style(Style::default().fg(Color::DarkGray)),
+                        .border_style(self.config.theme.muted),
                 );
 
-            f.render_widget(input, layout[1]);
+            f.render_widget(input, description_area);
         }
     }
 
     fn render_footer(&self, f: &mut Frame, area: Rect) {
+        let key = |action| self.config.keys.label(action).unwrap_or_else(|| "-".to_string());
+        let next = key(Action::NextField);
+        let prev = key(Action::PrevField);
+        let confirm = key(Action::Confirm);
+
         let keys_hint = if self.show_help {
             match self.current_field {
-                InputField::None => {
-                    "x: Settings  Tab: Focus field  Esc: Exit  Ctrl+C: Exit  Ctrl+Enter: Confirm"
-                }
-                InputField::Type | InputField::Scope => {
-                    "↑/↓/jk: Navigate  1-9: Direct selection  Tab: Next  Shift+Tab: Previous  Enter: Confirm"
-                }
-                InputField::Description | InputField::Body => {
-                    "←→/hl: Fields  Tab: Navigate  Enter: Confirm  Esc: Defocus"
-                }
-                InputField::BreakingToggle => {
-                    "Space: Toggle  Tab: Next  Shift+Tab: Previous  Enter: Confirm"
-                }
+                InputField::None => format!(
+                    "{}: Focus field  {}: History  {}: Presets  {}: Editor  Esc: Exit  {}: Exit  {}: Confirm",
+                    next,
+                    key(Action::History),
+                    key(Action::Presets),
+                    key(Action::OpenEditor),
+                    key(Action::Quit),
+                    confirm
+                ),
+                InputField::Type | InputField::Scope => format!(
+                    "↑/↓/jk: Navigate  1-9: Direct selection  {}: Next  {}: Previous  Enter: Confirm",
+                    next, prev
+                ),
+                InputField::Description => format!(
+                    "{}: Next  {}: Previous  {}: Editor  Enter: Confirm  Esc: Defocus",
+                    next,
+                    prev,
+                    key(Action::OpenEditor)
+                ),
+                InputField::Body => format!(
+                    "{}: Next  {}: Editor  {}: Confirm  Esc: Defocus",
+                    next,
+                    key(Action::OpenEditor),
+                    confirm
+                ),
+                InputField::BreakingToggle => format!(
+                    "Space/{}: Toggle  {}: Next  {}: Previous  Enter: Confirm",
+                    key(Action::ToggleBreaking),
+                    next,
+                    prev
+                ),
                 InputField::BreakingDescription => {
-                    "Type description  Enter: Confirm  Esc: Back"
+                    "Type description  Enter: Confirm  Esc: Back".to_string()
                 }
             }
         } else {
-            "Press 'h' for help"
+            "Press 'h' for help".to_string()
         };
 
         // Add mode indicator
-        let mode_indicator = format!(
-            "[{}]  ",
-            if self.config.dev_mode { "DEMO" } else { "GIT" }
-        );
+        let mode = if self.config.dev_mode { "DEMO" } else { "GIT" };
+        let mode_indicator = match &self.progress {
+            Some(label) => format!("[{} {}]  ", mode, label),
+            None => format!("[{}]  ", mode),
+        };
 
         let footer_text = format!("{}{}", mode_indicator, keys_hint);
 
         let footer = Paragraph::new(footer_text)
-            .style(Style::default().fg(Color::DarkGray))
+            .style(self.config.theme.muted)
             .alignment(Alignment::Center);
 
         f.render_widget(footer, area);
     }
-}
\ No newline at end of file
+}
+
+/// Splits a list area into the list and, when wide enough, a detail pane.
+fn split_detail_area(area: Rect) -> (Rect, Option<Rect>) {
+    if area.width < DETAIL_MIN_WIDTH {
+        return (area, None);
+    }
+    let layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(area);
+    (layout[0], Some(layout[1]))
+}
+
+/// Splits the breaking change area into the toggle and the description below it.
+fn split_breaking_area(area: Rect) -> (Rect, Rect) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Toggle (always visible)
+            Constraint::Length(3), // Description (always visible)
+        ])
+        .split(area);
+    (layout[0], layout[1])
+}
+
+/// Moves `index` by `delta` within `0..len`, wrapping around at both ends.
+fn wrap_index(index: usize, delta: i32, len: usize) -> usize {
+    (index as i64 + delta as i64).rem_euclid(len as i64) as usize
+}
+
+/// A rectangle of at most `width` x `height` centered in `area`.
+fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
@@ -0,0 +1,317 @@
+//! Wording checks for commit descriptions, enabled in the `[rules]` table:
+//! imperative mood, trailing period, letter case, banned words and spelling.
+//!
+//! The spell check uses a small bundled dictionary of base forms such as
+//! `add` or `rename`; plurals, past tenses and a few common prefixes and
+//! suffixes are derived from them. Words in backticks, identifiers, paths,
+//! acronyms and words with digits are never checked.
+
+use crate::config::{AppConfig, LetterCase};
+use regex::Regex;
+use std::{collections::HashSet, ops::Range, sync::LazyLock};
+
+static WORDS: &str = include_str!("words.txt");
+
+static DICTIONARY: LazyLock<HashSet<&'static str>> = LazyLock::new(|| WORDS.lines().collect());
+
+static TOKEN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"`[^`]*`?|[^\s`]+").unwrap());
+
+/// Verbs commonly starting a description, to recognize `added` or `fixes`.
+/// Words that are just as often nouns, like `test` or `log`, are left out.
+const VERBS: &[&str] = &[
+    "add", "address", "adjust", "allow", "apply", "avoid", "begin", "bind", "break", "bring", "build", "bump",
+    "change", "check", "choose", "clarify", "clean", "close", "complete", "configure", "consolidate", "convert",
+    "copy", "correct", "create", "decouple", "decrease", "deduplicate", "delete", "deploy", "deprecate",
+    "describe", "detect", "disable", "display", "do", "document", "downgrade", "draw", "drop", "emit", "enable",
+    "enforce", "ensure", "escape", "exclude", "explain", "export", "expose", "extend", "extract", "fetch", "fill",
+    "filter", "find", "fix", "flush", "freeze", "generate", "get", "give", "go", "guard", "handle", "hide",
+    "highlight", "hold", "ignore", "implement", "import", "improve", "include", "increase", "initialize",
+    "install", "introduce", "keep", "limit", "load", "make", "mark", "merge", "migrate", "move", "normalize",
+    "open", "optimize", "override", "parse", "pass", "persist", "polish", "prevent", "print", "prune", "publish",
+    "raise", "read", "rebuild", "redirect", "reduce", "refactor", "refresh", "register", "reload", "remove",
+    "rename", "render", "reorder", "replace", "reset", "resolve", "restore", "restructure", "retry", "return",
+    "reuse", "revert", "rework", "rewrite", "run", "sanitize", "save", "select", "send", "serialize", "set",
+    "show", "simplify", "skip", "sort", "speed", "split", "start", "stop", "store", "strip", "support",
+    "suppress", "switch", "sync", "take", "throw", "tidy", "toggle", "track", "translate", "trim", "truncate",
+    "tweak", "undo", "unify", "unlock", "update", "upgrade", "use", "validate", "verify", "warn", "wrap",
+    "write",
+];
+
+/// Irregular past and third-person forms of [`VERBS`], such as `did` and
+/// `does` for `do`.
+const IRREGULAR: [(&str, &str); 22] = [
+    ("began", "begin"), ("bound", "bind"), ("broke", "break"), ("brought", "bring"), ("built", "build"),
+    ("chose", "choose"), ("did", "do"), ("does", "do"), ("drew", "draw"), ("found", "find"), ("froze", "freeze"),
+    ("gave", "give"), ("got", "get"), ("held", "hold"), ("hid", "hide"), ("kept", "keep"), ("made", "make"),
+    ("ran", "run"), ("rebuilt", "rebuild"), ("rewrote", "rewrite"), ("sent", "send"), ("wrote", "write"),
+];
+
+/// `(suffix, replacements)` deriving inflected forms from base forms,
+/// `fixes` from `fix` or `used` from `use`. The first six are verb forms.
+const SUFFIXES: [(&str, &[&str]); 19] = [
+    ("s", &[""]),
+    ("es", &[""]),
+    ("ies", &["y"]),
+    ("ed", &["", "e"]),
+    ("ied", &["y"]),
+    ("ing", &["", "e"]),
+    ("er", &["", "e"]),
+    ("ers", &["", "e"]),
+    ("est", &["", "e"]),
+    ("ly", &["", "le"]),
+    ("ily", &["y"]),
+    ("ally", &[""]),
+    ("ness", &[""]),
+    ("ment", &[""]),
+    ("able", &["", "e"]),
+    ("ity", &["", "e"]),
+    ("ible", &["", "e"]),
+    ("ful", &[""]),
+    ("less", &[""]),
+];
+
+const PREFIXES: [&str; 12] = ["auto", "de", "dis", "mis", "multi", "non", "over", "pre", "re", "sub", "un", "under"];
+
+/// A wording problem in a description.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issue {
+    pub rule: &'static str,
+    pub message: String,
+    /// Bytes of the description the issue is about.
+    pub span: Range<usize>,
+}
+
+impl Issue {
+    fn new(rule: &'static str, message: impl Into<String>, span: Range<usize>) -> Self {
+        Self { rule, message: message.into(), span }
+    }
+}
+
+/// Checks `description` against the wording rules enabled in `config`.
+pub fn check(description: &str, config: &AppConfig) -> Vec<Issue> {
+    let rules = &config.rules;
+    let mut issues = Vec::new();
+    let tokens: Vec<(Range<usize>, &str)> = TOKEN
+        .find_iter(description)
+        .map(|m| (m.range(), m.as_str()))
+        .collect();
+
+    // Emoji and other symbols before the first word are skipped, code isn't checked
+    let first_word = tokens
+        .iter()
+        .find(|(_, token)| token.chars().any(char::is_alphanumeric))
+        .filter(|(_, token)| !token.starts_with('`'))
+        .and_then(|(span, token)| trim_word(span.clone(), token));
+
+    if rules.description_imperative
+        && let Some((span, word)) = first_word.clone()
+        && let Some(verb) = imperative_of(&word.to_lowercase())
+    {
+        issues.push(Issue::new(
+            "description-imperative",
+            format!("Use the imperative mood: `{}`, not `{}`", verb, word),
+            span,
+        ));
+    }
+
+    if let Some(case) = rules.description_case
+        && let Some((span, word)) = first_word
+    {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            // `README` or `iOS` keep their own case
+            let kept = chars.next().is_some_and(char::is_uppercase);
+            let issue = match case {
+                LetterCase::Lower if first.is_uppercase() && !kept => Some("lowercase"),
+                LetterCase::Upper if first.is_lowercase() && !kept => Some("uppercase"),
+                _ => None,
+            };
+            if let Some(case) = issue {
+                issues.push(Issue::new(
+                    "description-case",
+                    format!("Start the description with a {} letter", case),
+                    span.start..span.start + first.len_utf8(),
+                ));
+            }
+        }
+    }
+
+    let trimmed = description.trim_end();
+    if rules.description_no_period && trimmed.ends_with('.') && !trimmed.ends_with("..") {
+        issues.push(Issue::new(
+            "description-no-period",
+            "Don't end the description with a period",
+            trimmed.len() - 1..trimmed.len(),
+        ));
+    }
+
+    for banned in &rules.banned_words {
+        let boundary = |c: Option<char>| if c.is_some_and(char::is_alphanumeric) { r"\b" } else { "" };
+        let pattern = format!(
+            "(?i){}{}{}",
+            boundary(banned.chars().next()),
+            regex::escape(banned),
+            boundary(banned.chars().last())
+        );
+        let Ok(regex) = Regex::new(&pattern) else {
+            continue;
+        };
+        for found in regex.find_iter(description) {
+            issues.push(Issue::new(
+                "banned-words",
+                format!("`{}` is not allowed", found.as_str()),
+                found.range(),
+            ));
+        }
+    }
+
+    if rules.spell_check {
+        let project = project_words(config);
+        for (span, token) in &tokens {
+            if token.starts_with('`') {
+                continue;
+            }
+            let Some((span, word)) = trim_word(span.clone(), token) else {
+                continue;
+            };
+            let mut offset = span.start;
+            for part in word.split('-') {
+                let part_span = offset..offset + part.len();
+                offset += part.len() + 1;
+                if !is_checked(part) {
+                    continue;
+                }
+                let lower = part.to_lowercase();
+                let banned = issues.iter().any(|issue| issue.span == part_span);
+                if banned || project.contains(&lower) || is_known(&lower) {
+                    continue;
+                }
+                let message = match closest(&lower) {
+                    Some(suggestion) => format!("Unknown word `{}`, did you mean `{}`?", part, suggestion),
+                    None => format!("Unknown word `{}`", part),
+                };
+                issues.push(Issue::new("spell-check", message, part_span));
+            }
+        }
+    }
+
+    issues.sort_by_key(|issue| issue.span.start);
+    issues
+}
+
+/// Strips the punctuation around a token, `(fixes,` becomes `fixes`.
+fn trim_word(span: Range<usize>, token: &str) -> Option<(Range<usize>, &str)> {
+    let is_edge = |c: char| !c.is_alphanumeric();
+    let start = token.len() - token.trim_start_matches(is_edge).len();
+    let word = token.trim_matches(is_edge);
+    if word.is_empty() {
+        return None;
+    }
+    let start = span.start + start;
+    Some((start..start + word.len(), word))
+}
+
+/// Plain words only: identifiers, paths, numbers and acronyms are skipped.
+fn is_checked(word: &str) -> bool {
+    word.chars().count() > 1
+        && word.chars().all(|c| c.is_ascii_alphabetic() || c == '\'')
+        && !word.chars().skip(1).any(|c| c.is_ascii_uppercase())
+}
+
+fn project_words(config: &AppConfig) -> HashSet<String> {
+    config
+        .rules
+        .words
+        .iter()
+        .chain(&config.types)
+        .chain(&config.scopes)
+        .chain(config.packages.iter().map(|p| &p.name))
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+/// The base form of `word` when it is `added`, `adds` or `adding`.
+fn imperative_of(word: &str) -> Option<&'static str> {
+    if VERBS.contains(&word) {
+        return None;
+    }
+    if let Some((_, verb)) = IRREGULAR.iter().find(|(form, _)| *form == word) {
+        return Some(verb);
+    }
+    stems(word, &SUFFIXES[..6])
+        .iter()
+        .find_map(|stem| VERBS.iter().find(|verb| **verb == stem).copied())
+}
+
+fn is_known(word: &str) -> bool {
+    let word = word.trim_end_matches("'s").trim_end_matches('\'');
+    let in_dictionary = |word: &str| stems(word, &SUFFIXES).iter().any(|stem| DICTIONARY.contains(stem.as_str()));
+    in_dictionary(word)
+        || PREFIXES.iter().any(|prefix| {
+            word.strip_prefix(prefix)
+                .is_some_and(|rest| rest.len() >= 3 && in_dictionary(rest.trim_start_matches('-')))
+        })
+}
+
+/// `word` and the base forms it may be derived from.
+fn stems(word: &str, suffixes: &[(&str, &[&str])]) -> Vec<String> {
+    let mut stems = vec![word.to_string()];
+    for (suffix, replacements) in suffixes {
+        let Some(stem) = word.strip_suffix(suffix).filter(|stem| stem.len() >= 2) else {
+            continue;
+        };
+        stems.extend(replacements.iter().map(|r| format!("{}{}", stem, r)));
+        // `stopped` and `running` double the final consonant
+        let mut chars = stem.chars().rev();
+        if let (Some(last), Some(before)) = (chars.next(), chars.next())
+            && last == before
+            && !"aeiou".contains(last)
+        {
+            stems.push(stem[..stem.len() - 1].to_string());
+        }
+    }
+    stems
+}
+
+/// The nearest dictionary word, at most one edit away for short words and
+/// two for longer ones.
+fn closest(word: &str) -> Option<&'static str> {
+    let max = if word.len() <= 4 { 1 } else { 2 };
+    WORDS
+        .lines()
+        .filter(|candidate| candidate.len().abs_diff(word.len()) <= max)
+        .map(|candidate| (distance(word, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max)
+        // `teh` is closer to `the` than to `tea`
+        .min_by_key(|(distance, candidate)| (*distance, !is_anagram(word, candidate)))
+        .map(|(_, candidate)| candidate)
+}
+
+fn is_anagram(a: &str, b: &str) -> bool {
+    let sorted = |word: &str| {
+        let mut chars: Vec<char> = word.chars().collect();
+        chars.sort_unstable();
+        chars
+    };
+    sorted(a) == sorted(b)
+}
+
+/// Edit distance, counting a swap of two adjacent letters as one edit.
+fn distance(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    rows[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            rows[i][j] = (rows[i - 1][j] + 1).min(rows[i][j - 1] + 1).min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                rows[i][j] = rows[i][j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    rows[a.len()][b.len()]
+}